@@ -1,6 +1,7 @@
 use clap::Parser;
 use deadpool_postgres::{Config, ManagerConfig, RecyclingMethod, Runtime};
 use meos::prelude::*;
+use meos_polars::expr::make_tseq;
 use polars::prelude::*;
 use std::error::Error;
 use std::io;
@@ -117,9 +118,21 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let start = Instant::now();
     let df = LazyCsvReader::new_paths(data.into())
         .has_header(true)
+        .with_try_parse_dates(true)
         .finish()?;
     let full_size = df.clone().collect().unwrap().height();
 
+    // the first positions in time of each vessel, sorted the same way in each
+    // column so they stay aligned
+    let truncate = |e: Expr| match opts.max_trip_size {
+        Some(max) => e
+            .sort_by(
+                [col("T")],
+                SortMultipleOptions::default().with_maintain_order(true),
+            )
+            .head(Some(max)),
+        None => e,
+    };
     let df = df
         .select([
             col("MMSI"),
@@ -129,11 +142,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
         ])
         .group_by(["MMSI"])
         .agg([
-            // todo;; figure out how to remove dupes in df, the
-            //        trick is deriving the correct len afterwards
             len(),
-            col("T").sort(false),
-            concat_str([col("LON"), col("LAT")], " ", true).alias("P"),
+            make_tseq(
+                truncate(col("LON")),
+                truncate(col("LAT")),
+                truncate(col("T")),
+            )
+            .alias("trip"),
         ])
         .filter(col("len").gt(lit(opts.min_trip_size)))
         .sort("len", Default::default())
@@ -157,80 +172,45 @@ async fn main() -> Result<(), Box<dyn Error>> {
             let client = pool.get().await?;
             create_trip_table(&client).await?;
             let statement =
-                "INSERT INTO ais.trips (MMSI, trip) VALUES ($1, public.tgeompointFromBinary($2)) ON CONFLICT (MMSI) DO UPDATE SET trip = public.update(trips.trip, EXCLUDED.trip, true)";
+                "INSERT INTO ais.trips (MMSI, trip) VALUES ($1, $2) ON CONFLICT (MMSI) DO UPDATE SET trip = public.update(trips.trip, EXCLUDED.trip, true)";
             client.prepare(&statement).await.expect("prepare")
         };
 
-        use AnyValue::*;
-        if let [m, l, t, p] = df.get_columns() {
-            'df: for i in 0..sz {
-                let mut metric_trip_sz = 0;
-                match (m.get(i)?, l.get(i)?, t.get(i)?, p.get(i)?) {
-                    (Int64(mmsi), UInt32(len), List(ts), List(pt)) => {
-                        // println!("========== {i}: {mmsi} - {len} ==========");
-                        let source: Vec<_> = (0..len as usize)
-                            .into_iter()
-                            .map(|i| (ts.get(i).unwrap(), pt.get(i).unwrap()))
-                            .collect();
-
-                        for chunk in source.chunks(opts.batch_size) {
-                            let mut trip = vec![];
-
-                            for (t, p) in chunk {
-                                // todo;; remove dupes in df and this check goes away
-                                let ts = t.get_str().unwrap();
-                                if trip.last().is_some_and(|(pts, _)| pts == ts) {
-                                    continue;
-                                }
-                                // println!("\t {i}: Point({p})@{t}+00");
-                                // print!(".");
-                                trip.push((ts.to_string(), make_posit(ts, p.get_str().unwrap())));
-
-                                metric_total_posit_cnt += 1;
-                                metric_trip_sz += 1;
-                                if opts.max_trip_size.is_some_and(|max| metric_trip_sz >= max) {
-                                    break;
-                                }
-                            }
-
-                            if !trip.is_empty() {
-                                // todo;; remove dupes in df, and this map goes away
-                                let geoms: Vec<_> = trip.into_iter().map(|(_, g)| g).collect();
-                                let seq = TSeq::make(&geoms).unwrap();
-
-                                let bytes = seq.as_bytes();
-                                let client = pool.get().await?;
-                                match client
-                                    .execute(&insert_statement, &[&(mmsi as i32), &bytes])
-                                    .await
-                                {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        eprintln!("\nerror: {e}");
-                                        break 'df;
-                                    }
-                                }
-                            }
-                        }
-                        metric_mmsi_cnt += 1;
-                        if metric_mmsi_cnt % 500 == 0 {
-                            print!(".");
-                            let _ = io::stdout().flush();
-                        }
-                        if metric_total_posit_cnt - metric_last_posit_report > 10000 {
-                            metric_last_posit_report = metric_total_posit_cnt;
-                            print!("+");
-                            let _ = io::stdout().flush();
-                        }
-                    }
-                    x => {
-                        println!("fail- {x:?}");
-                    }
+        let mmsis = df.column("MMSI")?.i64()?;
+        let trips = df.column("trip")?.binary()?;
+        'trips: for (mmsi, wkb) in mmsis.into_iter().zip(trips) {
+            let (Some(mmsi), Some(wkb)) = (mmsi, wkb) else {
+                continue;
+            };
+            let mut instants = TSeq::from_wkb(wkb)?.instants();
+            metric_total_posit_cnt += instants.len();
+
+            // each batch is merged into the stored trip by the upsert
+            let client = pool.get().await?;
+            while !instants.is_empty() {
+                let rest = instants.split_off(opts.batch_size.clamp(1, instants.len()));
+                let seq = TSeq::make(&instants).ok_or("tseq::make failed")?;
+                instants = rest;
+                if let Err(e) = client
+                    .execute(&insert_statement, &[&(mmsi as i32), &seq])
+                    .await
+                {
+                    eprintln!("\nerror: {e}");
+                    break 'trips;
                 }
             }
+
+            metric_mmsi_cnt += 1;
+            if metric_mmsi_cnt % 500 == 0 {
+                print!(".");
+                let _ = io::stdout().flush();
+            }
+            if metric_total_posit_cnt - metric_last_posit_report > 10000 {
+                metric_last_posit_report = metric_total_posit_cnt;
+                print!("+");
+                let _ = io::stdout().flush();
+            }
         }
-    } else {
-        unreachable!("col mismatch")
     }
 
     let duration = start.elapsed();
@@ -242,7 +222,3 @@ async fn main() -> Result<(), Box<dyn Error>> {
     meos::finalize();
     Ok(())
 }
-
-fn make_posit(t: &str, p: &str) -> TInst {
-    TInst::from_wkt(&format!("SRID=4326;Point({p})@{t}+00")).expect("")
-}
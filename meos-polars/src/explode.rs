@@ -0,0 +1,121 @@
+use meos::prelude::Temporal;
+use polars::prelude::*;
+
+use crate::namespace::datetime_series;
+use crate::{map_temporals, MeosSeries};
+
+/// Explodes a temporal column into one row per instant
+///
+/// Temporal points give `t`, `x` and `y` columns, plus `z` when any point has
+/// one, temporal numbers give `t` and `value`. The `keys` columns are repeated
+/// on each row of their temporal and null temporals give no rows.
+pub fn explode_instants(df: &DataFrame, column: &str, keys: &[&str]) -> PolarsResult<DataFrame> {
+    let s = df.column(column)?;
+    let ty = s.meos().temporal_type()?;
+    if let Some(ty) = ty {
+        polars_ensure!(
+            ty.is_point() || ty.is_number(),
+            ComputeError: "cannot explode a {} column", ty
+        );
+    }
+
+    let is_point = ty.is_some_and(|ty| ty.is_point());
+
+    let instants = map_temporals(s, |t| Some(t.instants()))?;
+    let mut rows: Vec<IdxSize> = vec![];
+    let mut t = vec![];
+    let (mut x, mut y, mut z, mut v) = (vec![], vec![], vec![], vec![]);
+    for (i, insts) in instants.into_iter().enumerate() {
+        for inst in insts.into_iter().flatten() {
+            rows.push(i as IdxSize);
+            t.push(inst.start_timestamp().unix_micros());
+            if is_point {
+                let coords = inst.coords();
+                x.push(coords.map(|(px, _, _)| px));
+                y.push(coords.map(|(_, py, _)| py));
+                z.push(coords.and_then(|(_, _, pz)| pz));
+            } else {
+                v.push(inst.value_at(inst.start_timestamp()));
+            }
+        }
+    }
+
+    let mut out = df
+        .select(keys.iter().copied())?
+        .take(&IdxCa::from_vec("", rows))?;
+    out.with_column(datetime_series("t", t))?;
+    if is_point {
+        out.with_column(Float64Chunked::from_iter_options("x", x.into_iter()).into_series())?;
+        out.with_column(Float64Chunked::from_iter_options("y", y.into_iter()).into_series())?;
+        if z.iter().any(Option::is_some) {
+            out.with_column(Float64Chunked::from_iter_options("z", z.into_iter()).into_series())?;
+        }
+    } else {
+        out.with_column(Float64Chunked::from_iter_options("value", v.into_iter()).into_series())?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use meos::prelude::TemporalType;
+    use meos::{finalize, init};
+    use polars::prelude::*;
+
+    use crate::{explode_instants, MeosSeries};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    #[test]
+    fn test_explode_points() {
+        let trips = Series::new(
+            "trip",
+            &[
+                Some("[POINT(1 1)@2000-01-01, POINT(2 2)@2000-01-02]"),
+                None,
+                Some("POINT(5 5)@2000-01-01"),
+            ],
+        )
+        .meos()
+        .from_wkt(TemporalType::TGeomPoint)
+        .unwrap();
+        let df = DataFrame::new(vec![Series::new("id", &[1, 2, 3]), trips]).unwrap();
+
+        let rows = explode_instants(&df, "trip", &["id"]).unwrap();
+        assert_eq!(vec!["id", "t", "x", "y"], rows.get_column_names());
+        assert_eq!(
+            vec![Some(1), Some(1), Some(3)],
+            rows.column("id").unwrap().i32().unwrap().to_vec()
+        );
+        assert_eq!(
+            vec![Some(1.0), Some(2.0), Some(5.0)],
+            rows.column("x").unwrap().f64().unwrap().to_vec()
+        );
+    }
+
+    #[test]
+    fn test_explode_numbers() {
+        let speeds = Series::new("speed", &["[1.5@2000-01-01, 2.5@2000-01-02]"])
+            .meos()
+            .from_wkt(TemporalType::TFloat)
+            .unwrap();
+        let df = DataFrame::new(vec![speeds]).unwrap();
+
+        let rows = explode_instants(&df, "speed", &[]).unwrap();
+        assert_eq!(vec!["t", "value"], rows.get_column_names());
+        assert_eq!(
+            vec![Some(1.5), Some(2.5)],
+            rows.column("value").unwrap().f64().unwrap().to_vec()
+        );
+    }
+}
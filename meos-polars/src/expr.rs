@@ -0,0 +1,317 @@
+//! Polars expressions over temporal columns
+
+use meos::prelude::{BoundingBox, Error, TInst, TPoint, TSeq, Temporal, Wkb};
+use meos::stbox::STBox;
+use meos::tz::Timestamp;
+use polars::prelude::*;
+
+use crate::namespace::datetime_series;
+use crate::{map_temporals, meos_err, temporal_series, MeosSeries, TEMPORAL_DTYPE};
+
+/// Builds a tgeompoint sequence from `lon`, `lat` and `t` columns
+///
+/// Meant for a group by aggregation, each group becomes one linear sequence in
+/// SRID 4326. `t` is a datetime or unix microseconds column, the positions are
+/// sorted by time and only the first of equal timestamps is kept. Rows with a null
+/// are skipped and a group without positions is null.
+pub fn make_tseq(lon: Expr, lat: Expr, t: Expr) -> Expr {
+    apply_multiple(
+        |s| {
+            let [lon, lat, t] = s else {
+                polars_bail!(ComputeError: "make_tseq takes lon, lat and t columns");
+            };
+            let seq = build_tseq(lon, lat, t)?;
+            Ok(Some(temporal_series(lon.name(), std::iter::once(seq))))
+        },
+        [lon, lat, t],
+        GetOutput::from_type(TEMPORAL_DTYPE),
+        true,
+    )
+}
+
+fn build_tseq(lon: &Series, lat: &Series, t: &Series) -> PolarsResult<Option<TSeq>> {
+    let lon = lon.cast(&DataType::Float64)?;
+    let lat = lat.cast(&DataType::Float64)?;
+    let t = t.cast(&DataType::Datetime(TimeUnit::Microseconds, None))?;
+    let t = t.to_physical_repr();
+
+    let mut posits: Vec<_> = lon
+        .f64()?
+        .into_iter()
+        .zip(lat.f64()?)
+        .zip(t.i64()?)
+        .filter_map(|((x, y), t)| Some((t?, x?, y?)))
+        .collect();
+    posits.sort_by_key(|(t, _, _)| *t);
+    posits.dedup_by_key(|(t, _, _)| *t);
+    if posits.is_empty() {
+        return Ok(None);
+    }
+
+    let instants = posits
+        .into_iter()
+        .map(|(t, x, y)| {
+            let t = Timestamp::from_unix_micros(t).ok_or(Error::TimestampOutOfRange)?;
+            TInst::from_point(x, y, 4326, t)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(meos_err)?;
+    Ok(TSeq::make(&instants))
+}
+
+/// The length travelled by each temporal point, see [TPoint::length]
+pub fn length(expr: Expr) -> Expr {
+    expr.map(
+        |s| {
+            let values = map_temporals(&s, |t| t.length().ok())?;
+            Ok(Some(
+                Float64Chunked::from_iter_options(s.name(), values.into_iter()).into_series(),
+            ))
+        },
+        GetOutput::from_type(DataType::Float64),
+    )
+}
+
+/// The speed of each temporal point as a tfloat, see [TPoint::speed]
+pub fn speed(expr: Expr) -> Expr {
+    map_to_temporal(expr, |t| t.speed().ok())
+}
+
+/// The duration of each temporal, see [Temporal::duration]
+pub fn duration(expr: Expr, ignore_gaps: bool) -> Expr {
+    expr.map(
+        move |s| s.meos().duration(ignore_gaps).map(Some),
+        GetOutput::from_type(DataType::Duration(TimeUnit::Microseconds)),
+    )
+}
+
+/// Each temporal point restricted to a box, null when they do not intersect
+pub fn at_stbox(expr: Expr, b: &STBox) -> Expr {
+    let wkb = b.to_wkb();
+    expr.map(
+        move |s| {
+            let b = STBox::from_wkb(&wkb).map_err(meos_err)?;
+            let values = map_temporals(&s, |t| t.at_stbox(&b).ok().flatten())?;
+            Ok(Some(temporal_series(s.name(), values.into_iter())))
+        },
+        GetOutput::from_type(TEMPORAL_DTYPE),
+    )
+}
+
+/// A tbool per row, true when the two temporal points are within `dist`
+pub fn tdwithin(a: Expr, b: Expr, dist: f64) -> Expr {
+    map_multiple(
+        move |s| {
+            let [a, b] = s else {
+                polars_bail!(ComputeError: "tdwithin takes two temporal columns");
+            };
+            let values = a
+                .binary()?
+                .into_iter()
+                .zip(b.binary()?)
+                .map(|wkb| match wkb {
+                    (Some(a), Some(b)) => {
+                        let a = <Box<dyn Temporal>>::from_wkb(a).map_err(meos_err)?;
+                        let b = <Box<dyn Temporal>>::from_wkb(b).map_err(meos_err)?;
+                        a.tdwithin(b.as_ref(), dist).map_err(meos_err)
+                    }
+                    _ => Ok(None),
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            Ok(Some(temporal_series(a.name(), values.into_iter())))
+        },
+        [a, b],
+        GetOutput::from_type(TEMPORAL_DTYPE),
+    )
+}
+
+/// Each temporal simplified with Douglas-Peucker, see [Temporal::simplify_dp]
+pub fn simplify_dp(expr: Expr, eps_dist: f64, synchronized: bool) -> Expr {
+    map_to_temporal(expr, move |t| t.simplify_dp(eps_dist, synchronized).ok())
+}
+
+/// The bounding box of each temporal as a struct
+///
+/// The fields are `xmin`, `ymin`, `xmax`, `ymax`, `tmin` and `tmax`. Temporal
+/// numbers fill `xmin` and `xmax` with their value extent, other temporals
+/// without a spatial box only fill the time fields.
+pub fn bounding_box(expr: Expr) -> Expr {
+    expr.map(
+        |s| {
+            let boxes = map_temporals(&s, |t| t.bounding_box().ok())?;
+            let mut x = (vec![], vec![], vec![], vec![]);
+            let mut t = (vec![], vec![]);
+            for b in boxes {
+                let (xmin, ymin, xmax, ymax, tmin, tmax) = match b {
+                    Some(BoundingBox::STBox(b)) => {
+                        (b.xmin(), b.ymin(), b.xmax(), b.ymax(), b.tmin(), b.tmax())
+                    }
+                    Some(BoundingBox::TBox(b)) => {
+                        (b.xmin(), None, b.xmax(), None, b.tmin(), b.tmax())
+                    }
+                    Some(BoundingBox::Period(p)) => {
+                        (None, None, None, None, Some(p.lower()), Some(p.upper()))
+                    }
+                    None => (None, None, None, None, None, None),
+                };
+                x.0.push(xmin);
+                x.1.push(ymin);
+                x.2.push(xmax);
+                x.3.push(ymax);
+                t.0.push(tmin.and_then(|t| t.unix_micros()));
+                t.1.push(tmax.and_then(|t| t.unix_micros()));
+            }
+            let fields = [
+                Float64Chunked::from_iter_options("xmin", x.0.into_iter()).into_series(),
+                Float64Chunked::from_iter_options("ymin", x.1.into_iter()).into_series(),
+                Float64Chunked::from_iter_options("xmax", x.2.into_iter()).into_series(),
+                Float64Chunked::from_iter_options("ymax", x.3.into_iter()).into_series(),
+                datetime_series("tmin", t.0),
+                datetime_series("tmax", t.1),
+            ];
+            Ok(Some(StructChunked::new(s.name(), &fields)?.into_series()))
+        },
+        GetOutput::from_type(DataType::Struct(box_fields())),
+    )
+}
+
+fn box_fields() -> Vec<Field> {
+    let datetime = DataType::Datetime(TimeUnit::Microseconds, Some("UTC".into()));
+    vec![
+        Field::new("xmin", DataType::Float64),
+        Field::new("ymin", DataType::Float64),
+        Field::new("xmax", DataType::Float64),
+        Field::new("ymax", DataType::Float64),
+        Field::new("tmin", datetime.clone()),
+        Field::new("tmax", datetime),
+    ]
+}
+
+/// The value of each temporal number at `t`, see [Temporal::value_at]
+pub fn value_at(expr: Expr, t: impl Into<Timestamp>) -> Expr {
+    let t = t.into();
+    expr.map(
+        move |s| {
+            let values = map_temporals(&s, |v| v.value_at(t))?;
+            Ok(Some(
+                Float64Chunked::from_iter_options(s.name(), values.into_iter()).into_series(),
+            ))
+        },
+        GetOutput::from_type(DataType::Float64),
+    )
+}
+
+/// maps each temporal of a column to a temporal
+fn map_to_temporal(
+    expr: Expr,
+    f: impl Fn(Box<dyn Temporal>) -> Option<Box<dyn Temporal>> + Send + Sync + 'static,
+) -> Expr {
+    expr.map(
+        move |s| {
+            let values = map_temporals(&s, &f)?;
+            Ok(Some(temporal_series(s.name(), values.into_iter())))
+        },
+        GetOutput::from_type(TEMPORAL_DTYPE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use meos::{finalize, init};
+    use polars::prelude::*;
+
+    use meos::prelude::TemporalType;
+    use meos::tz::Timestamp;
+
+    use crate::expr::{bounding_box, duration, length, make_tseq, value_at};
+    use crate::MeosSeries;
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    const DAY: i64 = 86_400_000_000;
+
+    #[test]
+    fn test_make_tseq() {
+        let df = df!(
+            "id" => [1, 1, 1, 2],
+            "lon" => [2.0, 1.0, 1.5, 5.0],
+            "lat" => [2.0, 1.0, 1.5, 5.0],
+            "t" => [DAY, 0, DAY, 0],
+        )
+        .unwrap();
+        let trips = df
+            .lazy()
+            .group_by([col("id")])
+            .agg([make_tseq(col("lon"), col("lat"), col("t")).alias("trip")])
+            .sort("id", Default::default())
+            .collect()
+            .unwrap();
+
+        let trip = trips.column("trip").unwrap();
+        let n = trip.meos().num_instants().unwrap();
+        assert_eq!(vec![Some(2), Some(1)], n.u32().unwrap().to_vec());
+
+        let start = trip.meos().start_timestamp().unwrap();
+        let end = trip.meos().end_timestamp().unwrap();
+        assert_eq!(Some(0), start.datetime().unwrap().get(0));
+        assert_eq!(Some(DAY), end.datetime().unwrap().get(0));
+    }
+
+    #[test]
+    fn test_analytics() {
+        let wkt = Series::new(
+            "trip",
+            &[
+                "[POINT(0 0)@2000-01-01, POINT(3 4)@2000-01-02]",
+                "[POINT(1 1)@2000-01-01, POINT(1 2)@2000-01-03]",
+            ],
+        );
+        let trips = wkt.meos().from_wkt(TemporalType::TGeomPoint).unwrap();
+        let df = DataFrame::new(vec![trips])
+            .unwrap()
+            .lazy()
+            .select([
+                length(col("trip")).alias("length"),
+                duration(col("trip"), false).alias("duration"),
+                bounding_box(col("trip")).alias("box"),
+            ])
+            .collect()
+            .unwrap();
+
+        let len = df.column("length").unwrap().f64().unwrap();
+        assert_eq!(vec![Some(5.0), Some(1.0)], len.to_vec());
+        let d = df.column("duration").unwrap().duration().unwrap();
+        assert_eq!(Some(2 * DAY), d.get(1));
+
+        let b = df.column("box").unwrap().struct_().unwrap();
+        let xmax = b.field_by_name("xmax").unwrap();
+        assert_eq!(vec![Some(3.0), Some(1.0)], xmax.f64().unwrap().to_vec());
+
+        let speeds = Series::new(
+            "speed",
+            &["[1@2000-01-01 00:00:00+00, 3@2000-01-03 00:00:00+00]"],
+        );
+        let speeds = speeds.meos().from_wkt(TemporalType::TFloat).unwrap();
+        let t = Timestamp::from_micros(DAY);
+        let v = DataFrame::new(vec![speeds])
+            .unwrap()
+            .lazy()
+            .select([value_at(col("speed"), t)])
+            .collect()
+            .unwrap();
+        assert_eq!(
+            vec![Some(2.0)],
+            v.column("speed").unwrap().f64().unwrap().to_vec()
+        );
+    }
+}
@@ -1,14 +1,48 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
+//! Polars extension for meos temporals
+//!
+//! A temporal column is a `Binary` column holding the meos extended WKB of each
+//! value. The WKB carries the base type, subtype and SRID of the temporal, so the
+//! column needs no other metadata, see [MeosNameSpace::temporal_type]. Polars
+//! 0.40 has no field metadata to record them in anyway, and a column may mix
+//! base types and SRIDs.
+//!
+//! meos must be initialized with [meos::init] before any of these are used.
+
+use meos::prelude::{Error, Temporal, Wkb};
+use polars::prelude::*;
+
+mod explode;
+pub mod expr;
+mod namespace;
+
+pub use explode::explode_instants;
+pub use namespace::{MeosNameSpace, MeosSeries};
+
+/// The dtype of a temporal column
+pub const TEMPORAL_DTYPE: DataType = DataType::Binary;
+
+pub(crate) fn meos_err(e: Error) -> PolarsError {
+    PolarsError::ComputeError(format!("meos: {e}").into())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// applies `f` to each temporal of a column, nulls stay null
+pub(crate) fn map_temporals<T>(
+    s: &Series,
+    f: impl Fn(Box<dyn Temporal>) -> Option<T>,
+) -> PolarsResult<Vec<Option<T>>> {
+    s.binary()?
+        .into_iter()
+        .map(|wkb| match wkb {
+            Some(wkb) => <Box<dyn Temporal>>::from_wkb(wkb).map(&f).map_err(meos_err),
+            None => Ok(None),
+        })
+        .collect()
+}
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
+/// a temporal column from its values
+pub(crate) fn temporal_series<T: Wkb>(
+    name: &str,
+    values: impl Iterator<Item = Option<T>>,
+) -> Series {
+    BinaryChunked::from_iter_options(name, values.map(|t| t.map(|t| t.to_wkb()))).into_series()
 }
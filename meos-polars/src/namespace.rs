@@ -0,0 +1,172 @@
+use meos::prelude::{temporal_from_wkt, Temporal, TemporalType, Wkb};
+use polars::prelude::*;
+
+use crate::{map_temporals, meos_err, temporal_series};
+
+/// Access to the temporal operations of a series
+pub trait MeosSeries {
+    fn meos(&self) -> MeosNameSpace<'_>;
+}
+
+impl MeosSeries for Series {
+    fn meos(&self) -> MeosNameSpace<'_> {
+        MeosNameSpace(self)
+    }
+}
+
+/// Temporal operations on a series, see [MeosSeries::meos]
+pub struct MeosNameSpace<'a>(&'a Series);
+
+impl MeosNameSpace<'_> {
+    /// parses a String column of temporals of the given base type
+    pub fn from_wkt(&self, ty: TemporalType) -> PolarsResult<Series> {
+        let values = self
+            .0
+            .str()?
+            .into_iter()
+            .map(|wkt| wkt.map(|wkt| temporal_from_wkt(wkt, ty)).transpose())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(meos_err)?;
+        Ok(temporal_series(self.0.name(), values.into_iter()))
+    }
+
+    /// parses a String column of hex encoded WKB
+    pub fn from_hex(&self) -> PolarsResult<Series> {
+        let values = self
+            .0
+            .str()?
+            .into_iter()
+            .map(|hex| {
+                hex.map(|hex| {
+                    let wkb = decode_hex(hex)?;
+                    <Box<dyn Temporal>>::from_wkb(&wkb).map_err(meos_err)
+                })
+                .transpose()
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        Ok(temporal_series(self.0.name(), values.into_iter()))
+    }
+
+    /// the hex encoded WKB of each temporal
+    pub fn as_hex(&self) -> PolarsResult<Series> {
+        let values = map_temporals(self.0, |t| t.as_hex())?;
+        Ok(StringChunked::from_iter_options(self.0.name(), values.into_iter()).into_series())
+    }
+
+    /// the MF-JSON of each temporal
+    pub fn as_mf_json(&self) -> PolarsResult<Series> {
+        let values = map_temporals(self.0, |t| t.to_mf_json().ok())?;
+        Ok(StringChunked::from_iter_options(self.0.name(), values.into_iter()).into_series())
+    }
+
+    /// the base type shared by the temporals, `None` when the column is all null
+    pub fn temporal_type(&self) -> PolarsResult<Option<TemporalType>> {
+        let types = map_temporals(self.0, |t| Some(t.temporal_type()))?;
+        let types = types
+            .into_iter()
+            .flatten()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(meos_err)?;
+        let mut types = types.into_iter();
+        let first = types.next();
+        polars_ensure!(
+            types.all(|t| Some(t) == first),
+            ComputeError: "column {} mixes temporal base types", self.0.name()
+        );
+        Ok(first)
+    }
+
+    pub fn num_instants(&self) -> PolarsResult<Series> {
+        let values = map_temporals(self.0, |t| Some(t.num_instants() as u32))?;
+        Ok(UInt32Chunked::from_iter_options(self.0.name(), values.into_iter()).into_series())
+    }
+
+    /// the first timestamp of each temporal as a UTC datetime
+    pub fn start_timestamp(&self) -> PolarsResult<Series> {
+        let values = map_temporals(self.0, |t| t.start_timestamp().unix_micros())?;
+        Ok(datetime_series(self.0.name(), values))
+    }
+
+    /// the last timestamp of each temporal as a UTC datetime
+    pub fn end_timestamp(&self) -> PolarsResult<Series> {
+        let values = map_temporals(self.0, |t| t.end_timestamp().unix_micros())?;
+        Ok(datetime_series(self.0.name(), values))
+    }
+
+    /// the duration of each temporal, see [Temporal::duration]
+    pub fn duration(&self, ignore_gaps: bool) -> PolarsResult<Series> {
+        let values = map_temporals(self.0, |t| t.duration(ignore_gaps).total_micros())?;
+        Ok(
+            Int64Chunked::from_iter_options(self.0.name(), values.into_iter())
+                .into_duration(TimeUnit::Microseconds)
+                .into_series(),
+        )
+    }
+}
+
+pub(crate) fn datetime_series(name: &str, micros: Vec<Option<i64>>) -> Series {
+    Int64Chunked::from_iter_options(name, micros.into_iter())
+        .into_datetime(TimeUnit::Microseconds, Some("UTC".into()))
+        .into_series()
+}
+
+fn decode_hex(hex: &str) -> PolarsResult<Vec<u8>> {
+    polars_ensure!(hex.len() % 2 == 0, ComputeError: "odd length hex wkb");
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| polars_err!(ComputeError: "invalid hex wkb: {}", e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use meos::prelude::TemporalType;
+    use meos::{finalize, init};
+    use polars::prelude::*;
+
+    use crate::MeosSeries;
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    #[test]
+    fn test_from_wkt() {
+        let wkt = Series::new(
+            "trip",
+            &[
+                Some("[POINT(1 1)@2000-01-01, POINT(2 2)@2000-01-02]"),
+                None,
+                Some("POINT(1 1)@2000-01-01"),
+            ],
+        );
+        let trips = wkt.meos().from_wkt(TemporalType::TGeomPoint).unwrap();
+        assert_eq!(&DataType::Binary, trips.dtype());
+        assert_eq!(1, trips.null_count());
+        assert_eq!(
+            Some(TemporalType::TGeomPoint),
+            trips.meos().temporal_type().unwrap()
+        );
+
+        let n = trips.meos().num_instants().unwrap();
+        assert_eq!(vec![Some(2), None, Some(1)], n.u32().unwrap().to_vec());
+
+        let d = trips.meos().duration(false).unwrap();
+        assert_eq!(Some(86_400_000_000), d.duration().unwrap().get(0));
+
+        let hex = trips.meos().as_hex().unwrap();
+        let back = hex.meos().from_hex().unwrap();
+        assert!(back.meos().num_instants().unwrap().equals_missing(&n));
+    }
+}
@@ -5,8 +5,10 @@ use std::fs::File;
 use std::io::Write;
 use std::str::FromStr;
 
+use chrono::NaiveDateTime;
 use clap::Parser;
 use meos::prelude::*;
+use meos::tz::Timestamp;
 
 #[derive(Debug, serde::Deserialize)]
 struct AisRecord {
@@ -83,8 +85,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     {
         let rec: AisRecord = result?;
 
-        let lon_lat = format!("{} {}", rec.longitude, rec.latitude);
-        let posit = make_posit(&rec.t, &lon_lat);
+        let posit = make_posit(&rec.t, rec.longitude, rec.latitude);
         vtype.insert(rec.mmsi, rec.vessel_type.unwrap_or(0));
         match trips.entry(rec.mmsi) {
             Entry::Occupied(mut trip) => {
@@ -143,6 +144,13 @@ fn write_record(
     Ok(())
 }
 
-fn make_posit(t: &str, p: &str) -> TInst {
-    TInst::from_wkt(&format!("SRID=4326;Point({p})@{t}+00")).expect("posit")
+fn make_posit(t: &str, lon: f64, lat: f64) -> TInst {
+    TInst::from_point(lon, lat, 4326, parse_time(t).expect("time")).expect("posit")
+}
+
+/// AIS times are UTC without an offset, e.g. `2021-01-08 00:00:00`
+fn parse_time(t: &str) -> Result<Timestamp, Box<dyn Error>> {
+    let t: NaiveDateTime = t.replacen(' ', "T", 1).parse()?;
+    Timestamp::from_unix_micros(t.and_utc().timestamp_micros())
+        .ok_or_else(|| "time out of range".into())
 }
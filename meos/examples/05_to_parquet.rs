@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow::array::{Int64Array, UInt32Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use chrono::NaiveDateTime;
+use clap::Parser;
+use meos::parquet::{read_trips, write_trips};
+use meos::prelude::*;
+use meos::tz::Timestamp;
+
+#[derive(Debug, serde::Deserialize)]
+struct AisRecord {
+    #[serde(alias = "BaseDateTime")]
+    t: String,
+    #[serde(alias = "MMSI")]
+    mmsi: i64,
+    #[serde(alias = "LAT")]
+    latitude: f64,
+    #[serde(alias = "LON")]
+    longitude: f64,
+    #[serde(alias = "VesselType", default)]
+    vessel_type: Option<u32>,
+}
+
+#[derive(Clone, Debug, Parser)]
+struct Opts {
+    /// Path to the input CSV file
+    input: String,
+
+    /// Path to the output GeoParquet file
+    output: String,
+
+    /// Maximum number of records to read from input
+    #[clap(short, long)]
+    limit: Option<usize>,
+
+    /// Filter out trips with less than this number posits
+    #[clap(long, default_value = "1")]
+    min_trip_size: usize,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+
+    meos::init();
+
+    let mut rdr = csv::Reader::from_reader(File::open(&opts.input)?);
+    let mut posits: BTreeMap<i64, (u32, Vec<TInst>)> = BTreeMap::new();
+    for result in rdr.deserialize().take(opts.limit.unwrap_or(usize::MAX)) {
+        let rec: AisRecord = result?;
+        let posit = TInst::from_point(rec.longitude, rec.latitude, 4326, parse_time(&rec.t)?)?;
+        let (vtype, trip) = posits.entry(rec.mmsi).or_default();
+        *vtype = rec.vessel_type.unwrap_or(*vtype);
+        match trip.last() {
+            Some(prev) if *prev >= posit => continue,
+            _ => trip.push(posit),
+        }
+    }
+
+    let (mut mmsi, mut vtype, mut trips) = (vec![], vec![], vec![]);
+    for (id, (vt, trip)) in posits {
+        if trip.len() < opts.min_trip_size {
+            continue;
+        }
+        if let Some(seq) = TSeq::make(&trip) {
+            mmsi.push(id);
+            vtype.push(vt);
+            trips.push(seq);
+        }
+    }
+    let attributes = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("mmsi", DataType::Int64, false),
+            Field::new("vessel_type", DataType::UInt32, false),
+        ])),
+        vec![
+            Arc::new(Int64Array::from(mmsi)),
+            Arc::new(UInt32Array::from(vtype)),
+        ],
+    )?;
+    write_trips(File::create(&opts.output)?, &trips, Some(&attributes))?;
+
+    let (back, _) = read_trips::<TSeq>(File::open(&opts.output)?)?;
+    println!("Wrote {} trips to {}", back.len(), opts.output);
+
+    meos::finalize();
+
+    Ok(())
+}
+
+/// AIS times are UTC without an offset, e.g. `2021-01-08 00:00:00`
+fn parse_time(t: &str) -> Result<Timestamp, Box<dyn Error>> {
+    let t: NaiveDateTime = t.replacen(' ', "T", 1).parse()?;
+    Timestamp::from_unix_micros(t.and_utc().timestamp_micros())
+        .ok_or_else(|| "time out of range".into())
+}
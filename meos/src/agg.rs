@@ -0,0 +1,597 @@
+//! Streaming temporal aggregates
+//!
+//! Aggregators are consumed and returned by [Aggregate::add] so they can be
+//! driven by [Iterator::try_fold], partial aggregates computed in parallel are
+//! merged with [Combine::combine].
+//!
+//! ```ignore
+//! let active = trips.iter().try_fold(TCountAgg::new(), |a, t| a.add(t))?.finish();
+//! ```
+
+use std::ptr::null_mut;
+
+use libc::free;
+use meos_sys as ffi;
+
+use crate::error::Error;
+use crate::stbox::STBox;
+use crate::temp::{ensure_number, temp_from, Temporal};
+use crate::tpoint::ensure_point;
+use crate::{TPtr, TPtrCtr};
+
+pub trait Aggregate: Sized {
+    type Output;
+
+    /// adds a temporal to the aggregate, an error for a base type it doesn't
+    /// aggregate
+    fn add<T: Temporal + ?Sized>(self, t: &T) -> Result<Self, Error>;
+
+    /// completes the aggregate, returning `None` when nothing was aggregated
+    fn finish(self) -> Option<Self::Output>;
+}
+
+/// Aggregates whose partial states can be merged
+pub trait Combine: Aggregate {
+    fn combine(self, other: Self) -> Self;
+}
+
+/// meos skiplist aggregate state
+struct SkipList(*mut ffi::SkipList);
+
+impl SkipList {
+    fn new() -> Self {
+        Self(null_mut())
+    }
+
+    fn update<F>(&mut self, f: F)
+    where
+        F: FnOnce(*mut ffi::SkipList) -> *mut ffi::SkipList,
+    {
+        self.0 = f(self.0);
+    }
+
+    /// `f` frees the skiplist, so it is taken out of the state before the call
+    fn finish<F>(mut self, f: F) -> Option<Box<dyn Temporal>>
+    where
+        F: FnOnce(*mut ffi::SkipList) -> TPtr,
+    {
+        let state = std::mem::replace(&mut self.0, null_mut());
+        if state.is_null() {
+            return None;
+        }
+        temp_from(f(state))
+    }
+}
+
+impl Drop for SkipList {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { ffi::skiplist_free(self.0) }
+        }
+    }
+}
+
+fn is_tint(t: TPtr) -> bool {
+    unsafe { (*t).temptype == ffi::meosType_T_TINT as u8 }
+}
+
+/// Number of temporals defined at each instant, as a tint
+pub struct TCountAgg {
+    state: SkipList,
+}
+
+impl TCountAgg {
+    pub fn new() -> Self {
+        Self {
+            state: SkipList::new(),
+        }
+    }
+}
+
+impl Default for TCountAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Aggregate for TCountAgg {
+    type Output = Box<dyn Temporal>;
+
+    fn add<T: Temporal + ?Sized>(mut self, t: &T) -> Result<Self, Error> {
+        self.state
+            .update(|s| unsafe { ffi::temporal_tcount_transfn(s, t.ptr()) });
+        Ok(self)
+    }
+
+    fn finish(self) -> Option<Self::Output> {
+        self.state
+            .finish(|s| unsafe { ffi::temporal_tagg_finalfn(s) })
+    }
+}
+
+impl Combine for TCountAgg {
+    fn combine(mut self, other: Self) -> Self {
+        // partial counts are summed
+        if let Some(o) = other.finish() {
+            self.state
+                .update(|s| unsafe { ffi::tint_tsum_transfn(s, o.ptr()) });
+        }
+        self
+    }
+}
+
+/// Minimum value at each instant of tint or tfloat temporals
+pub struct TMinAgg {
+    state: SkipList,
+}
+
+impl TMinAgg {
+    pub fn new() -> Self {
+        Self {
+            state: SkipList::new(),
+        }
+    }
+
+    /// `t` is a temporal number
+    fn transfn<T: Temporal + ?Sized>(&mut self, t: &T) {
+        self.state.update(|s| unsafe {
+            if is_tint(t.ptr()) {
+                ffi::tint_tmin_transfn(s, t.ptr())
+            } else {
+                ffi::tfloat_tmin_transfn(s, t.ptr())
+            }
+        });
+    }
+}
+
+impl Default for TMinAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Aggregate for TMinAgg {
+    type Output = Box<dyn Temporal>;
+
+    fn add<T: Temporal + ?Sized>(mut self, t: &T) -> Result<Self, Error> {
+        ensure_number(t)?;
+        self.transfn(t);
+        Ok(self)
+    }
+
+    fn finish(self) -> Option<Self::Output> {
+        self.state
+            .finish(|s| unsafe { ffi::temporal_tagg_finalfn(s) })
+    }
+}
+
+impl Combine for TMinAgg {
+    fn combine(mut self, other: Self) -> Self {
+        if let Some(o) = other.finish() {
+            self.transfn(o.as_ref());
+        }
+        self
+    }
+}
+
+/// Maximum value at each instant of tint or tfloat temporals
+pub struct TMaxAgg {
+    state: SkipList,
+}
+
+impl TMaxAgg {
+    pub fn new() -> Self {
+        Self {
+            state: SkipList::new(),
+        }
+    }
+
+    /// `t` is a temporal number
+    fn transfn<T: Temporal + ?Sized>(&mut self, t: &T) {
+        self.state.update(|s| unsafe {
+            if is_tint(t.ptr()) {
+                ffi::tint_tmax_transfn(s, t.ptr())
+            } else {
+                ffi::tfloat_tmax_transfn(s, t.ptr())
+            }
+        });
+    }
+}
+
+impl Default for TMaxAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Aggregate for TMaxAgg {
+    type Output = Box<dyn Temporal>;
+
+    fn add<T: Temporal + ?Sized>(mut self, t: &T) -> Result<Self, Error> {
+        ensure_number(t)?;
+        self.transfn(t);
+        Ok(self)
+    }
+
+    fn finish(self) -> Option<Self::Output> {
+        self.state
+            .finish(|s| unsafe { ffi::temporal_tagg_finalfn(s) })
+    }
+}
+
+impl Combine for TMaxAgg {
+    fn combine(mut self, other: Self) -> Self {
+        if let Some(o) = other.finish() {
+            self.transfn(o.as_ref());
+        }
+        self
+    }
+}
+
+/// Average value at each instant of tint or tfloat temporals, as a tfloat
+///
+/// Kept as a running sum and count rather than the meos tavg state so that
+/// partial aggregates can be combined.
+pub struct TAvgAgg {
+    sum: SkipList,
+    count: TCountAgg,
+}
+
+impl TAvgAgg {
+    pub fn new() -> Self {
+        Self {
+            sum: SkipList::new(),
+            count: TCountAgg::new(),
+        }
+    }
+}
+
+impl Default for TAvgAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Aggregate for TAvgAgg {
+    type Output = Box<dyn Temporal>;
+
+    fn add<T: Temporal + ?Sized>(mut self, t: &T) -> Result<Self, Error> {
+        ensure_number(t)?;
+        self.sum.update(|s| unsafe {
+            if is_tint(t.ptr()) {
+                let f = ffi::tint_to_tfloat(t.ptr());
+                let s = ffi::tfloat_tsum_transfn(s, f);
+                free(f.cast());
+                s
+            } else {
+                ffi::tfloat_tsum_transfn(s, t.ptr())
+            }
+        });
+        self.count = self.count.add(t)?;
+        Ok(self)
+    }
+
+    fn finish(self) -> Option<Self::Output> {
+        let sum = self
+            .sum
+            .finish(|s| unsafe { ffi::temporal_tagg_finalfn(s) })?;
+        let count = self.count.finish()?;
+        unsafe {
+            let count = ffi::tint_to_tfloat(count.ptr());
+            let avg = ffi::div_tnumber_tnumber(sum.ptr(), count);
+            free(count.cast());
+            temp_from(avg)
+        }
+    }
+}
+
+impl Combine for TAvgAgg {
+    fn combine(mut self, other: Self) -> Self {
+        if let Some(o) = other
+            .sum
+            .finish(|s| unsafe { ffi::temporal_tagg_finalfn(s) })
+        {
+            self.sum
+                .update(|s| unsafe { ffi::tfloat_tsum_transfn(s, o.ptr()) });
+        }
+        self.count = self.count.combine(other.count);
+        self
+    }
+}
+
+/// Time weighted centroid at each instant of temporal points
+///
+/// The meos centroid state carries running coordinate sums which are not
+/// exposed, so unlike the other aggregates this one cannot be combined.
+pub struct TCentroidAgg {
+    state: SkipList,
+}
+
+impl TCentroidAgg {
+    pub fn new() -> Self {
+        Self {
+            state: SkipList::new(),
+        }
+    }
+}
+
+impl Default for TCentroidAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Aggregate for TCentroidAgg {
+    type Output = Box<dyn Temporal>;
+
+    fn add<T: Temporal + ?Sized>(mut self, t: &T) -> Result<Self, Error> {
+        ensure_point(t)?;
+        self.state
+            .update(|s| unsafe { ffi::tpoint_tcentroid_transfn(s, t.ptr()) });
+        Ok(self)
+    }
+
+    fn finish(self) -> Option<Self::Output> {
+        self.state
+            .finish(|s| unsafe { ffi::tpoint_tcentroid_finalfn(s) })
+    }
+}
+
+/// Merge of temporals into a single temporal
+pub struct TMergeAgg {
+    state: TPtr,
+}
+
+impl TMergeAgg {
+    pub fn new() -> Self {
+        Self { state: null_mut() }
+    }
+}
+
+impl Default for TMergeAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TMergeAgg {
+    fn drop(&mut self) {
+        unsafe {
+            free(self.state.cast());
+        }
+    }
+}
+
+impl Aggregate for TMergeAgg {
+    type Output = Box<dyn Temporal>;
+
+    fn add<T: Temporal + ?Sized>(mut self, t: &T) -> Result<Self, Error> {
+        // only temporals of the same base type merge
+        if !self.state.is_null() && unsafe { (*self.state).temptype != (*t.ptr()).temptype } {
+            return Err(Error::WrongTemporalType);
+        }
+        unsafe {
+            let merged = ffi::temporal_merge(self.state, t.ptr());
+            free(self.state.cast());
+            self.state = merged;
+        }
+        Ok(self)
+    }
+
+    fn finish(mut self) -> Option<Self::Output> {
+        temp_from(std::mem::replace(&mut self.state, null_mut()))
+    }
+}
+
+impl Combine for TMergeAgg {
+    fn combine(mut self, mut other: Self) -> Self {
+        unsafe {
+            let merged = ffi::temporal_merge(self.state, other.state);
+            free(self.state.cast());
+            self.state = merged;
+        }
+        other.state = null_mut();
+        self
+    }
+}
+
+/// Spatiotemporal extent of temporal points
+pub struct ExtentAgg {
+    state: *mut ffi::STBox,
+}
+
+impl ExtentAgg {
+    pub fn new() -> Self {
+        Self { state: null_mut() }
+    }
+}
+
+impl Default for ExtentAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ExtentAgg {
+    fn drop(&mut self) {
+        unsafe {
+            free(self.state.cast());
+        }
+    }
+}
+
+impl Aggregate for ExtentAgg {
+    type Output = STBox;
+
+    fn add<T: Temporal + ?Sized>(mut self, t: &T) -> Result<Self, Error> {
+        ensure_point(t)?;
+        self.state = unsafe { ffi::tpoint_extent_transfn(self.state, t.ptr()) };
+        Ok(self)
+    }
+
+    fn finish(mut self) -> Option<Self::Output> {
+        STBox::from_ptr(std::mem::replace(&mut self.state, null_mut())).ok()
+    }
+}
+
+impl Combine for ExtentAgg {
+    fn combine(mut self, other: Self) -> Self {
+        if self.state.is_null() {
+            return other;
+        }
+        if !other.state.is_null() {
+            unsafe {
+                let union = ffi::union_stbox_stbox(self.state, other.state, false);
+                free(self.state.cast());
+                self.state = union;
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agg::*;
+    use crate::prelude::*;
+    use crate::tz::Timestamp;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    const TWO_MINUTES: Timestamp = Timestamp::from_micros(120_000_000);
+    const SEVEN_MINUTES: Timestamp = Timestamp::from_micros(420_000_000);
+
+    fn numbers() -> Vec<TSeq> {
+        [
+            "[1@2000-01-01 00:00:00+00, 1@2000-01-01 00:10:00+00]",
+            "[2@2000-01-01 00:05:00+00, 2@2000-01-01 00:15:00+00]",
+        ]
+        .iter()
+        .map(|wkt| TSeq::from_wkt_as(wkt, TemporalType::TFloat).unwrap())
+        .collect()
+    }
+
+    fn points() -> Vec<TSeq> {
+        [
+            "[POINT(0 0)@2000-01-01 00:00:00+00, POINT(0 0)@2000-01-01 00:10:00+00]",
+            "[POINT(2 2)@2000-01-01 00:00:00+00, POINT(2 2)@2000-01-01 00:10:00+00]",
+        ]
+        .iter()
+        .map(|wkt| TSeq::from_wkt(wkt).unwrap())
+        .collect()
+    }
+
+    fn fold<A: Aggregate>(agg: A, temporals: &[TSeq]) -> Option<A::Output> {
+        temporals
+            .iter()
+            .try_fold(agg, |a, t| a.add(t))
+            .unwrap()
+            .finish()
+    }
+
+    #[test]
+    fn test_tcount() {
+        let count = fold(TCountAgg::new(), &numbers()).unwrap();
+        assert_eq!(TemporalType::TInt, count.temporal_type().unwrap());
+        assert_eq!(Some(1.0), count.value_at(TWO_MINUTES));
+        assert_eq!(Some(2.0), count.value_at(SEVEN_MINUTES));
+
+        let seqs = numbers();
+        let combined = TCountAgg::new()
+            .add(&seqs[0])
+            .unwrap()
+            .combine(TCountAgg::new().add(&seqs[1]).unwrap())
+            .finish()
+            .unwrap();
+        assert_eq!(Some(2.0), combined.value_at(SEVEN_MINUTES));
+
+        assert!(TCountAgg::new().finish().is_none());
+    }
+
+    #[test]
+    fn test_tmin_tmax() {
+        let min = fold(TMinAgg::new(), &numbers()).unwrap();
+        assert_eq!(Some(1.0), min.value_at(SEVEN_MINUTES));
+        let max = fold(TMaxAgg::new(), &numbers()).unwrap();
+        assert_eq!(Some(2.0), max.value_at(SEVEN_MINUTES));
+        assert_eq!(Some(1.0), max.value_at(TWO_MINUTES));
+
+        assert!(TMinAgg::new().finish().is_none());
+        assert!(TMaxAgg::new().finish().is_none());
+
+        let trip = &points()[0];
+        assert!(matches!(
+            TMinAgg::new().add(trip),
+            Err(Error::WrongTemporalType)
+        ));
+        assert!(TMaxAgg::new().add(trip).is_err());
+        assert!(TAvgAgg::new().add(trip).is_err());
+        assert!(TCentroidAgg::new().add(&numbers()[0]).is_err());
+    }
+
+    #[test]
+    fn test_tavg() {
+        let avg = fold(TAvgAgg::new(), &numbers()).unwrap();
+        assert_eq!(Some(1.0), avg.value_at(TWO_MINUTES));
+        assert_eq!(Some(1.5), avg.value_at(SEVEN_MINUTES));
+
+        let seqs = numbers();
+        let combined = TAvgAgg::new()
+            .add(&seqs[0])
+            .unwrap()
+            .combine(TAvgAgg::new().add(&seqs[1]).unwrap())
+            .finish()
+            .unwrap();
+        assert_eq!(Some(1.5), combined.value_at(SEVEN_MINUTES));
+
+        assert!(TAvgAgg::new().finish().is_none());
+    }
+
+    #[test]
+    fn test_tcentroid() {
+        let centroid = fold(TCentroidAgg::new(), &points()).unwrap();
+        assert_eq!(Some((1.0, 1.0, None)), centroid.instants()[0].coords());
+
+        assert!(TCentroidAgg::new().finish().is_none());
+    }
+
+    #[test]
+    fn test_tmerge_extent() {
+        let seqs = points();
+        let merged = fold(TMergeAgg::new(), &seqs[..1]).unwrap();
+        assert_eq!(2, merged.num_instants());
+        assert!(TMergeAgg::new().finish().is_none());
+
+        // disjoint in time, so the merge is a sequence set of both
+        let later = TSeq::from_wkt(
+            "[POINT(5 5)@2000-01-01 01:00:00+00, POINT(6 6)@2000-01-01 01:10:00+00]",
+        )
+        .unwrap();
+        let merged = fold(TMergeAgg::new(), &[seqs.into_iter().next().unwrap(), later]).unwrap();
+        assert_eq!(Type::SequenceSet, merged.ttype());
+        assert_eq!(4, merged.num_instants());
+        assert!(TMergeAgg::new()
+            .add(&points()[0])
+            .unwrap()
+            .add(&numbers()[0])
+            .is_err());
+
+        let seqs = points();
+
+        let extent = fold(ExtentAgg::new(), &seqs).unwrap();
+        assert_eq!(Some(0.0), extent.xmin());
+        assert_eq!(Some(2.0), extent.xmax());
+        assert!(ExtentAgg::new().finish().is_none());
+    }
+}
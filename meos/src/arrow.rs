@@ -0,0 +1,283 @@
+//! Apache Arrow conversions, enabled by the `arrow` feature
+//!
+//! A slice of temporals becomes a single column [RecordBatch], see [ArrowLayout]
+//! for the two column layouts. The base type and SRID are kept in the field
+//! metadata under `meos:type` and `meos:srid`, so all the temporals of a batch
+//! must share them.
+
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
+use std::sync::Arc;
+
+use ::arrow::array::{
+    Array, ArrayRef, AsArray, BinaryArray, Float64Array, ListArray, StructArray,
+    TimestampMicrosecondArray,
+};
+use ::arrow::buffer::OffsetBuffer;
+use ::arrow::datatypes::{
+    DataType, Field, Fields, Float64Type, Schema, TimeUnit, TimestampMicrosecondType,
+};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+
+use crate::error::Error;
+use crate::temp::Temporal;
+use crate::tinst::TInst;
+use crate::tpoint::TPoint;
+use crate::tseq::TSeq;
+use crate::tz::Timestamp;
+use crate::{Interp, TPtrCtr, TPtrFrom, Wkb};
+
+/// How temporals are laid out in a [RecordBatch]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ArrowLayout {
+    /// a binary column of meos extended WKB, for any temporal
+    Wkb,
+    /// a GeoArrow style `list<struct<t, x, y>>` column of the instants, for
+    /// 2D temporal points with linear interpolation, read back as linear
+    /// sequences
+    Points,
+}
+
+const TYPE_KEY: &str = "meos:type";
+const SRID_KEY: &str = "meos:srid";
+
+fn point_fields() -> Fields {
+    Fields::from(vec![
+        Field::new(
+            "t",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("x", DataType::Float64, false),
+        Field::new("y", DataType::Float64, false),
+    ])
+}
+
+/// converts temporals into a record batch with a single column `name`
+pub fn to_record_batch<T: Temporal>(
+    temporals: &[T],
+    name: &str,
+    layout: ArrowLayout,
+) -> Result<RecordBatch, ArrowError> {
+    let mut metadata = HashMap::new();
+    let mut first = None;
+    for t in temporals {
+        let ty = t
+            .temporal_type()
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        let key = (ty, t.srid().ok());
+        match first {
+            None => first = Some(key),
+            Some(first) if first != key => {
+                return Err(ArrowError::InvalidArgumentError(
+                    "the temporals of a batch must share the base type and SRID".into(),
+                ))
+            }
+            _ => {}
+        }
+    }
+    if let Some((ty, srid)) = first {
+        metadata.insert(TYPE_KEY.to_string(), ty.to_string());
+        if let Some(srid) = srid {
+            metadata.insert(SRID_KEY.to_string(), srid.to_string());
+        }
+    }
+
+    let column: ArrayRef = match layout {
+        ArrowLayout::Wkb => Arc::new(BinaryArray::from_iter_values(
+            temporals.iter().map(|t| t.to_wkb()),
+        )),
+        ArrowLayout::Points => Arc::new(points_array(temporals)?),
+    };
+    let field = Field::new(name, column.data_type().clone(), false).with_metadata(metadata);
+    RecordBatch::try_new(Arc::new(Schema::new(vec![field])), vec![column])
+}
+
+fn points_array<T: Temporal>(temporals: &[T]) -> Result<ListArray, ArrowError> {
+    let mut lengths = Vec::with_capacity(temporals.len());
+    let (mut t, mut x, mut y) = (vec![], vec![], vec![]);
+    for temporal in temporals {
+        let ty = temporal
+            .temporal_type()
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        if !ty.is_point() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "the points layout needs temporal points, not {ty}"
+            )));
+        }
+        if let Some(interp @ (Interp::Discrete | Interp::Step)) = temporal.interp() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "the points layout needs linear interpolation, not {interp}"
+            )));
+        }
+        let mut written = 0;
+        for inst in temporal.instants() {
+            let Some((px, py, pz)) = inst.coords() else {
+                continue;
+            };
+            if pz.is_some() {
+                return Err(ArrowError::InvalidArgumentError(
+                    "the points layout has no z, use the wkb layout".into(),
+                ));
+            }
+            let us = inst
+                .start_timestamp()
+                .unix_micros()
+                .ok_or_else(|| ArrowError::ExternalError(Box::new(Error::TimestampOutOfRange)))?;
+            t.push(us);
+            x.push(px);
+            y.push(py);
+            written += 1;
+        }
+        lengths.push(written);
+    }
+
+    let points = StructArray::new(
+        point_fields(),
+        vec![
+            Arc::new(TimestampMicrosecondArray::from(t).with_timezone("UTC")),
+            Arc::new(Float64Array::from(x)),
+            Arc::new(Float64Array::from(y)),
+        ],
+        None,
+    );
+    ListArray::try_new(
+        Arc::new(Field::new("item", DataType::Struct(point_fields()), false)),
+        OffsetBuffer::from_lengths(lengths),
+        Arc::new(points),
+        None,
+    )
+}
+
+/// restores the temporals of the first column of a record batch
+///
+/// The layout is detected from the column type, nulls are an error.
+pub fn from_record_batch<T: Temporal>(batch: &RecordBatch) -> Result<Vec<T>, ArrowError> {
+    let invalid = |e: Error| ArrowError::ExternalError(Box::new(e));
+    if batch.num_columns() == 0 {
+        return Ok(vec![]);
+    }
+    let column = batch.column(0);
+    if column.null_count() > 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "null temporals are not supported".into(),
+        ));
+    }
+
+    match column.data_type() {
+        DataType::Binary => column
+            .as_binary::<i32>()
+            .iter()
+            .flatten()
+            .map(|wkb| T::from_wkb(wkb).map_err(invalid))
+            .collect(),
+        DataType::List(_) => {
+            let srid = batch
+                .schema()
+                .field(0)
+                .metadata()
+                .get(SRID_KEY)
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+            let lists = column.as_list::<i32>();
+            (0..lists.len())
+                .map(|i| {
+                    let points = lists.value(i);
+                    let seq = points_seq(points.as_struct(), srid).map_err(invalid)?;
+                    // the sequence pointer moves into T
+                    let seq = ManuallyDrop::new(seq);
+                    T::from_ptr(seq.ptr()).map_err(invalid)
+                })
+                .collect()
+        }
+        t => Err(ArrowError::InvalidArgumentError(format!(
+            "unsupported temporal column type {t}"
+        ))),
+    }
+}
+
+fn points_seq(points: &StructArray, srid: i32) -> Result<TSeq, Error> {
+    let t = points.column(0).as_primitive::<TimestampMicrosecondType>();
+    let x = points.column(1).as_primitive::<Float64Type>();
+    let y = points.column(2).as_primitive::<Float64Type>();
+    let instants = (0..points.len())
+        .map(|i| {
+            let t = Timestamp::from_unix_micros(t.value(i)).ok_or(Error::TimestampOutOfRange)?;
+            TInst::from_point(x.value(i), y.value(i), srid, t)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // todo;; check the meos error
+    TSeq::make(&instants).ok_or(Error::MeosError(-999))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arrow::{from_record_batch, to_record_batch, ArrowLayout};
+    use crate::prelude::*;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    fn trips() -> Vec<TSeq> {
+        [
+            "SRID=4326;[POINT(1 1)@2000-01-01, POINT(2 2)@2000-01-02]",
+            "SRID=4326;[POINT(5 5)@2000-01-01, POINT(6 5)@2000-01-02, POINT(6 6)@2000-01-03]",
+        ]
+        .iter()
+        .map(|wkt| TSeq::from_wkt(wkt).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn test_wkb_roundtrip() {
+        let trips = trips();
+        let batch = to_record_batch(&trips, "trip", ArrowLayout::Wkb).unwrap();
+        assert_eq!(2, batch.num_rows());
+        assert_eq!(
+            Some(&"tgeompoint".to_string()),
+            batch.schema().field(0).metadata().get("meos:type")
+        );
+
+        let back: Vec<TSeq> = from_record_batch(&batch).unwrap();
+        assert!(trips == back);
+    }
+
+    #[test]
+    fn test_points_roundtrip() {
+        let trips = trips();
+        let batch = to_record_batch(&trips, "trip", ArrowLayout::Points).unwrap();
+        assert_eq!(2, batch.num_rows());
+
+        let back: Vec<TSeq> = from_record_batch(&batch).unwrap();
+        assert!(trips == back);
+        assert_eq!(4326, back[0].srid().unwrap());
+    }
+
+    #[test]
+    fn test_rejected() {
+        let mut mixed = trips();
+        mixed.push(TSeq::from_wkt("SRID=3857;[POINT(1 1)@2000-01-01 00:00:00+00]").unwrap());
+        assert!(to_record_batch(&mixed, "trip", ArrowLayout::Wkb).is_err());
+
+        let step = TSeq::from_wkt(
+            "SRID=4326;Interp=Step;[POINT(1 1)@2000-01-01 00:00:00+00, POINT(2 2)@2000-01-02 00:00:00+00]",
+        )
+        .unwrap();
+        assert_eq!(Some(Interp::Step), step.interp());
+        assert!(to_record_batch(&[step], "trip", ArrowLayout::Points).is_err());
+
+        let z = TSeq::from_wkt("SRID=4326;[POINT Z(1 1 1)@2000-01-01 00:00:00+00]").unwrap();
+        assert!(to_record_batch(&[z], "trip", ArrowLayout::Points).is_err());
+    }
+}
@@ -10,4 +10,20 @@ pub enum Error {
 
     #[error("ffi string conversion error {0}")]
     FfiStringError(String),
+
+    #[error("interval has no fixed duration")]
+    UnsupportedInterval,
+
+    #[error("duration does not fit a meos interval")]
+    IntervalOverflow,
+
+    #[error("timestamp out of range")]
+    TimestampOutOfRange,
+
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+
+    #[cfg(feature = "gpx")]
+    #[error("gpx error {0}")]
+    Gpx(#[from] ::gpx::errors::GpxError),
 }
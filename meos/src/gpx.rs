@@ -0,0 +1,221 @@
+//! GPX tracks, enabled by the `gpx` feature
+//!
+//! A track segment is a linear tgeompoint [TSeq] in SRID 4326 and a track is a
+//! [TSet] with one sequence per segment. Trackpoints without a time are skipped
+//! on import, as are points not later than the previous one, since a sequence
+//! needs increasing timestamps. Segments without any timed point are skipped
+//! and a track without any is an error. Exporting a temporal that is not a
+//! point, or an instant outside the years 1 to 9999, is an error too.
+
+use std::io::{Read, Write};
+
+use ::gpx::{Gpx, GpxVersion, Track, TrackSegment, Waypoint};
+
+use crate::error::Error;
+use crate::temp::Temporal;
+use crate::tinst::TInst;
+use crate::tpoint::ensure_point;
+use crate::tseq::TSeq;
+use crate::tset::TSet;
+use crate::tz::Timestamp;
+
+/// the timed trackpoints of a segment, dropping those not later than the
+/// previous one
+impl TryFrom<&TrackSegment> for TSeq {
+    type Error = Error;
+
+    fn try_from(segment: &TrackSegment) -> Result<Self, Self::Error> {
+        let mut instants: Vec<TInst> = vec![];
+        for p in &segment.points {
+            let Some(t) = p.time else { continue };
+            let t = Timestamp::from(time::OffsetDateTime::from(t));
+            if instants
+                .last()
+                .is_some_and(|prev| prev.start_timestamp() >= t)
+            {
+                continue;
+            }
+            let point = p.point();
+            instants.push(TInst::from_point(point.x(), point.y(), 4326, t)?);
+        }
+        if instants.is_empty() {
+            return Err(Error::InvalidInput(
+                "a track segment without timed points".into(),
+            ));
+        }
+        // todo;; check the meos error
+        TSeq::make(&instants).ok_or(Error::MeosError(-999))
+    }
+}
+
+impl TryFrom<&Track> for TSet {
+    type Error = Error;
+
+    fn try_from(track: &Track) -> Result<Self, Self::Error> {
+        let seqs = track
+            .segments
+            .iter()
+            .filter(|s| s.points.iter().any(|p| p.time.is_some()))
+            .map(TSeq::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        if seqs.is_empty() {
+            return Err(Error::InvalidInput("a track without timed points".into()));
+        }
+        TSet::make(&seqs)
+    }
+}
+
+/// a trackpoint per instant, an error for a temporal that is not a point or
+/// a timestamp beyond the years 1 to 9999 of GPX times
+impl TryFrom<&TSeq> for TrackSegment {
+    type Error = Error;
+
+    fn try_from(seq: &TSeq) -> Result<Self, Self::Error> {
+        ensure_point(seq)?;
+        Ok(TrackSegment {
+            points: seq
+                .instants()
+                .iter()
+                .map(waypoint)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&TSet> for Track {
+    type Error = Error;
+
+    fn try_from(set: &TSet) -> Result<Self, Self::Error> {
+        Ok(Track {
+            segments: set
+                .sequences()
+                .iter()
+                .map(TrackSegment::try_from)
+                .collect::<Result<_, _>>()?,
+            ..Default::default()
+        })
+    }
+}
+
+/// a timestamped trackpoint, the z of 3D points is the elevation
+fn waypoint(inst: &TInst) -> Result<Waypoint, Error> {
+    let (x, y, z) = inst.coords().ok_or(Error::WrongTemporalType)?;
+    let t = time::OffsetDateTime::try_from(inst.start_timestamp())?;
+    let mut p = Waypoint::new((x, y).into());
+    p.elevation = z;
+    p.time = Some(t.into());
+    Ok(p)
+}
+
+/// reads the tracks of a GPX document
+pub fn read_gpx<R: Read>(reader: R) -> Result<Vec<TSet>, Error> {
+    let gpx = ::gpx::read(reader)?;
+    gpx.tracks.iter().map(TSet::try_from).collect()
+}
+
+/// writes the tracks as a GPX 1.1 document
+pub fn write_gpx<W: Write>(writer: W, tracks: &[TSet]) -> Result<(), Error> {
+    let gpx = Gpx {
+        version: GpxVersion::Gpx11,
+        creator: Some("meos-rs".to_string()),
+        tracks: tracks
+            .iter()
+            .map(Track::try_from)
+            .collect::<Result<_, _>>()?,
+        ..Default::default()
+    };
+    Ok(::gpx::write(&gpx, writer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::gpx::TrackSegment;
+
+    use crate::gpx::{read_gpx, write_gpx};
+    use crate::prelude::*;
+    use crate::tz::Timestamp;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    const GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+  <trk>
+    <trkseg>
+      <trkpt lat="1" lon="2"><time>2000-01-01T00:00:00Z</time></trkpt>
+      <trkpt lat="1.5" lon="2.5"></trkpt>
+      <trkpt lat="2" lon="3"><time>2000-01-01T01:00:00Z</time></trkpt>
+    </trkseg>
+    <trkseg>
+      <trkpt lat="3" lon="4"><time>2000-01-01T02:00:00Z</time></trkpt>
+    </trkseg>
+  </trk>
+</gpx>"#;
+
+    #[test]
+    fn test_read() {
+        let tracks = read_gpx(GPX.as_bytes()).unwrap();
+        assert_eq!(1, tracks.len());
+        let seqs = tracks[0].sequences();
+        assert_eq!(2, seqs.len());
+        assert_eq!(2, seqs[0].num_instants());
+        assert_eq!(4326, seqs[0].srid().unwrap());
+        let expected = TSeq::from_wkt(
+            "SRID=4326;[POINT(2 1)@2000-01-01 00:00:00+00, POINT(3 2)@2000-01-01 01:00:00+00]",
+        )
+        .unwrap();
+        assert!(expected == seqs[0]);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let tracks = read_gpx(GPX.as_bytes()).unwrap();
+        let mut out = vec![];
+        write_gpx(&mut out, &tracks).unwrap();
+        let back = read_gpx(out.as_slice()).unwrap();
+        assert!(tracks[0] == back[0]);
+    }
+
+    #[test]
+    fn test_write_errors() {
+        let temps = TSeq::from_wkt_as(
+            "[1@2000-01-01 00:00:00+00, 2@2000-01-01 01:00:00+00]",
+            TemporalType::TFloat,
+        )
+        .unwrap();
+        assert!(matches!(
+            TrackSegment::try_from(&temps),
+            Err(Error::WrongTemporalType)
+        ));
+
+        // past the year 9999
+        let late = Timestamp::from_micros(320_000_000_000_000_000);
+        let seq = TSeq::make(&vec![TInst::from_point(2.0, 1.0, 4326, late).unwrap()]).unwrap();
+        assert!(matches!(
+            TrackSegment::try_from(&seq),
+            Err(Error::TimestampOutOfRange)
+        ));
+        let set = TSet::make(&[seq]).unwrap();
+        assert!(write_gpx(Vec::<u8>::new(), &[set]).is_err());
+    }
+
+    #[test]
+    fn test_untimed_track() {
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+  <trk><trkseg><trkpt lat="1" lon="2"></trkpt></trkseg></trk>
+</gpx>"#;
+        assert!(read_gpx(gpx.as_bytes()).is_err());
+        assert!(TSet::make(&[]).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::ffi::{CStr, CString};
 use std::fmt::{Display, Formatter};
-use std::ptr::null_mut;
+use std::ptr::{null_mut, NonNull};
 
 use libc::{c_char, free};
 
@@ -8,24 +8,84 @@ use crate::error::Error;
 use crate::error::Error::FfiStringError;
 use meos_sys as ffi;
 
+pub mod agg;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 mod error;
+#[cfg(feature = "gpx")]
+pub mod gpx;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "postgres-types")]
+mod postgres;
 pub mod prelude;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod set;
 pub mod span;
+#[cfg(feature = "sqlx")]
+mod sqlx;
 pub mod stbox;
 pub mod tbox;
 mod temp;
 mod tinst;
+mod tpoint;
 mod tseq;
 mod tset;
 pub mod tz;
 
 pub(crate) type TPtr = *mut ffi::Temporal;
 
+macro_rules! send_sync {
+    ($($t:ty),*) => {$(
+        // SAFETY: each value owns its meos allocation, which is freed only on
+        // drop and is passed to meos as a read only input from `&self`, so it
+        // can move between threads and be shared like a `Box<[u8]>`
+        unsafe impl Send for $t {}
+        unsafe impl Sync for $t {}
+    )*};
+}
+
+send_sync!(
+    tinst::TInst,
+    tseq::TSeq,
+    tset::TSet,
+    stbox::STBox,
+    tbox::TBox,
+    span::TsTzSpan,
+    span::IntSpan,
+    span::FloatSpan
+);
+
+/// Types with a meos well-known binary representation
+///
+/// The extended WKB variant is used so that the SRID of spatial types is kept.
+pub trait Wkb {
+    fn to_wkb(&self) -> Vec<u8>;
+
+    fn from_wkb(wkb: &[u8]) -> Result<Self, Error>
+    where
+        Self: Sized;
+
+    /// the WKB as a hex string
+    fn as_hex(&self) -> Option<String>;
+
+    fn from_hex(hex: &str) -> Result<Self, Error>
+    where
+        Self: Sized;
+}
+
 pub(crate) trait TPtrCtr {
     fn ptr(&self) -> TPtr;
 }
 
+pub(crate) trait TPtrFrom {
+    /// takes ownership of a meos allocated temporal, validating its subtype
+    fn from_ptr(ptr: TPtr) -> Result<Self, Error>
+    where
+        Self: Sized;
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Type {
     Instant,
@@ -44,6 +104,91 @@ impl Display for Type {
     }
 }
 
+/// Decimal digits of the WKT written by the `Display` impls, the meos default
+pub const DEFAULT_PRECISION: i32 = 15;
+
+/// The base type of a temporal
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TemporalType {
+    TBool,
+    TInt,
+    TFloat,
+    TText,
+    TGeomPoint,
+    TGeogPoint,
+}
+
+impl TemporalType {
+    /// the MobilityDB type names, in the order of the variants
+    pub(crate) const NAMES: [&'static str; 6] = [
+        "tbool",
+        "tint",
+        "tfloat",
+        "ttext",
+        "tgeompoint",
+        "tgeogpoint",
+    ];
+
+    pub(crate) fn from_meos(t: ffi::meosType) -> Option<Self> {
+        match t {
+            ffi::meosType_T_TBOOL => Some(TemporalType::TBool),
+            ffi::meosType_T_TINT => Some(TemporalType::TInt),
+            ffi::meosType_T_TFLOAT => Some(TemporalType::TFloat),
+            ffi::meosType_T_TTEXT => Some(TemporalType::TText),
+            ffi::meosType_T_TGEOMPOINT => Some(TemporalType::TGeomPoint),
+            ffi::meosType_T_TGEOGPOINT => Some(TemporalType::TGeogPoint),
+            _ => None,
+        }
+    }
+
+    pub fn is_point(&self) -> bool {
+        matches!(self, TemporalType::TGeomPoint | TemporalType::TGeogPoint)
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, TemporalType::TInt | TemporalType::TFloat)
+    }
+
+    /// the MobilityDB type name
+    pub(crate) fn name(&self) -> &'static str {
+        Self::NAMES[*self as usize]
+    }
+}
+
+impl Display for TemporalType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Interp {
+    Discrete,
+    Step,
+    Linear,
+}
+
+impl Display for Interp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Interp::Discrete => "Discrete",
+            Interp::Step => "Step",
+            Interp::Linear => "Linear",
+        };
+        f.write_str(s)
+    }
+}
+
+impl From<Interp> for ffi::interpType {
+    fn from(value: Interp) -> Self {
+        match value {
+            Interp::Discrete => ffi::interpType_DISCRETE,
+            Interp::Step => ffi::interpType_STEP,
+            Interp::Linear => ffi::interpType_LINEAR,
+        }
+    }
+}
+
 pub fn init() {
     unsafe {
         ffi::meos_initialize(null_mut(), None);
@@ -69,7 +214,36 @@ fn to_c_str(n: &str) -> Result<CString, Error> {
     CString::new(n.as_bytes()).map_err(|e| FfiStringError(format!("{e}")))
 }
 
+/// parses a geometry from (E)WKT, the caller owns the result
+fn geom_from_wkt(wkt: &str) -> Result<NonNull<ffi::GSERIALIZED>, Error> {
+    let cstr = to_c_str(wkt)?;
+    // todo;; check the meos error
+    NonNull::new(unsafe { ffi::pgis_geometry_in(cstr.as_ptr() as *mut c_char, -1) })
+        .ok_or(Error::MeosError(-999))
+}
+
+/// takes ownership of a meos allocated hex string
+unsafe fn take_hex(p: *mut c_char) -> Option<String> {
+    let s = c_str_to_slice(&(p as *const c_char)).map(|s| s.to_owned());
+    free(p.cast());
+    s
+}
+
+/// takes ownership of a meos allocated byte buffer
+unsafe fn take_bytes(p: *mut u8, size: usize) -> Vec<u8> {
+    if p.is_null() {
+        return vec![];
+    }
+    let v = std::slice::from_raw_parts(p, size).to_vec();
+    free(p.cast());
+    v
+}
+
 fn try_cstr_to_str(p: *mut c_char) -> Result<String, Error> {
+    if p.is_null() {
+        // todo;; check the meos error
+        return Err(Error::MeosError(-999));
+    }
     unsafe {
         let cstr = CStr::from_ptr(p);
         let s = CString::new(cstr.to_bytes())
@@ -0,0 +1,239 @@
+//! GeoParquet files of trips, enabled by the `parquet` feature
+//!
+//! Each row holds a temporal point as meos WKB in the `trip` column, its
+//! trajectory in the `geometry` GeoParquet column and any attribute columns.
+//! The trajectory is the meos trajectory of the trip, with its z if any, so the
+//! file can be previewed by any GeoParquet reader. All the trips of a file share
+//! the SRID of the file metadata.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use ::arrow::array::{ArrayRef, BinaryArray};
+use ::arrow::compute::concat_batches;
+use ::arrow::datatypes::{DataType, Field, Schema};
+use ::arrow::record_batch::RecordBatch;
+use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::errors::ParquetError;
+use ::parquet::file::metadata::KeyValue;
+use ::parquet::file::properties::WriterProperties;
+use ::parquet::file::reader::ChunkReader;
+use libc::free;
+use meos_sys as ffi;
+
+use crate::arrow::{from_record_batch, to_record_batch, ArrowLayout};
+use crate::error::Error;
+use crate::temp::Temporal;
+use crate::tpoint::TPoint;
+use crate::{take_hex, to_c_str, TPtrCtr};
+
+pub const TRIP_COLUMN: &str = "trip";
+pub const GEOMETRY_COLUMN: &str = "geometry";
+
+/// writes trips and their attributes, which need one row per trip
+pub fn write_trips<W: Write + Send, T: Temporal>(
+    writer: W,
+    trips: &[T],
+    attributes: Option<&RecordBatch>,
+) -> Result<(), ParquetError> {
+    let external = |e: Error| ParquetError::External(Box::new(e));
+    let srids = trips
+        .iter()
+        .map(|t| t.srid())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(external)?;
+    if srids.windows(2).any(|w| w[0] != w[1]) {
+        return Err(ParquetError::General(
+            "the trips of a file must share their SRID".into(),
+        ));
+    }
+
+    let trip = to_record_batch(trips, TRIP_COLUMN, ArrowLayout::Wkb)?;
+    let geometry = trips
+        .iter()
+        .map(trajectory_wkb)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(external)?;
+    let geometry: ArrayRef = Arc::new(BinaryArray::from_iter_values(geometry));
+
+    let mut fields: Vec<Field> = vec![];
+    let mut columns: Vec<ArrayRef> = vec![];
+    if let Some(attributes) = attributes {
+        fields.extend(
+            attributes
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.as_ref().clone()),
+        );
+        columns.extend(attributes.columns().iter().cloned());
+    }
+    fields.push(trip.schema().field(0).clone());
+    columns.push(trip.column(0).clone());
+    fields.push(Field::new(GEOMETRY_COLUMN, DataType::Binary, false));
+    columns.push(geometry);
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?;
+
+    let srid = srids.first().copied().unwrap_or(4326);
+    let props = WriterProperties::builder()
+        .set_key_value_metadata(Some(vec![KeyValue::new(
+            "geo".to_string(),
+            geo_metadata(srid),
+        )]))
+        .build();
+    let mut w = ArrowWriter::try_new(writer, batch.schema(), Some(props))?;
+    w.write(&batch)?;
+    w.close()?;
+    Ok(())
+}
+
+/// reads the trips of a file written by [write_trips] and their attributes
+///
+/// The reader is a [std::fs::File] or the file in memory as `bytes::Bytes`.
+pub fn read_trips<T: Temporal>(
+    reader: impl ChunkReader + 'static,
+) -> Result<(Vec<T>, RecordBatch), ParquetError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    let schema = builder.schema().clone();
+    let trip = schema.index_of(TRIP_COLUMN)?;
+    let attributes: Vec<usize> = (0..schema.fields().len())
+        .filter(|&i| ![TRIP_COLUMN, GEOMETRY_COLUMN].contains(&schema.field(i).name().as_str()))
+        .collect();
+
+    let mut trips = vec![];
+    let mut batches = vec![];
+    for batch in builder.build()? {
+        let batch = batch?;
+        trips.extend(from_record_batch::<T>(&batch.project(&[trip])?)?);
+        batches.push(batch.project(&attributes)?);
+    }
+    let attributes = Arc::new(schema.project(&attributes)?);
+    Ok((trips, concat_batches(&attributes, &batches)?))
+}
+
+/// the GeoParquet file metadata of the geometry column
+fn geo_metadata(srid: i32) -> String {
+    // lon/lat 4326 is the GeoParquet default of OGC:CRS84, a null crs is unknown
+    let crs = match srid {
+        4326 => String::new(),
+        0 => r#","crs":null"#.to_string(),
+        srid => format!(r#","crs":{{"id":{{"authority":"EPSG","code":{srid}}}}}"#),
+    };
+    // trajectories are points, lines or collections of them, so the types are
+    // left open
+    format!(
+        r#"{{"version":"1.0.0","primary_column":"{GEOMETRY_COLUMN}","columns":{{"{GEOMETRY_COLUMN}":{{"encoding":"WKB","geometry_types":[]{crs}}}}}}}"#
+    )
+}
+
+/// the little endian WKB of the meos trajectory of a temporal point
+///
+/// The SRID is cleared first, it is in the file metadata and not part of WKB.
+fn trajectory_wkb<T: Temporal>(t: &T) -> Result<Vec<u8>, Error> {
+    t.srid()?;
+    let endian = to_c_str("NDR")?;
+    let hex = unsafe {
+        let plain = ffi::tpoint_set_srid(t.ptr(), 0);
+        // todo;; check the meos error
+        if plain.is_null() {
+            return Err(Error::MeosError(-999));
+        }
+        let gs = ffi::tpoint_trajectory(plain);
+        free(plain.cast());
+        if gs.is_null() {
+            return Err(Error::MeosError(-999));
+        }
+        let hex = take_hex(ffi::geo_as_hexewkb(gs, endian.as_ptr()));
+        free(gs.cast());
+        hex.ok_or(Error::MeosError(-999))?
+    };
+    // meos only returns the geometry WKB as a bytea or as hex, the hex string
+    // carries its own length
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<_>>()
+        .ok_or(Error::MeosError(-999))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::sync::Arc;
+
+    use ::arrow::array::{Array, AsArray, Int64Array};
+    use ::arrow::datatypes::{DataType, Field, Int64Type, Schema};
+    use ::arrow::record_batch::RecordBatch;
+
+    use crate::parquet::{geo_metadata, read_trips, write_trips};
+    use crate::prelude::*;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let trips: Vec<TSeq> = [
+            "SRID=4326;[POINT(1 1)@2000-01-01, POINT(2 2)@2000-01-02]",
+            "SRID=4326;[POINT(5 5)@2000-01-01, POINT(6 6)@2000-01-03]",
+        ]
+        .iter()
+        .map(|wkt| TSeq::from_wkt(wkt).unwrap())
+        .collect();
+        let mmsi = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new(
+                "mmsi",
+                DataType::Int64,
+                false,
+            )])),
+            vec![Arc::new(Int64Array::from(vec![1, 2]))],
+        )
+        .unwrap();
+
+        let path = std::env::temp_dir().join(format!("meos_trips_{}.parquet", std::process::id()));
+        write_trips(File::create(&path).unwrap(), &trips, Some(&mmsi)).unwrap();
+        let (back, attributes) = read_trips::<TSeq>(File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut buf = vec![];
+        write_trips(&mut buf, &trips, None).unwrap();
+        let (in_memory, _) = read_trips::<TSeq>(bytes::Bytes::from(buf)).unwrap();
+        assert!(trips == in_memory);
+
+        assert!(trips == back);
+        assert_eq!(1, attributes.num_columns());
+        let ids = attributes.column(0).as_primitive::<Int64Type>();
+        assert_eq!(2, ids.len());
+        assert_eq!(2, ids.value(1));
+    }
+
+    #[test]
+    fn test_srid() {
+        let trips: Vec<TSeq> = [
+            "SRID=4326;[POINT(1 1)@2000-01-01 00:00:00+00]",
+            "SRID=3857;[POINT(5 5)@2000-01-01 00:00:00+00]",
+        ]
+        .iter()
+        .map(|wkt| TSeq::from_wkt(wkt).unwrap())
+        .collect();
+        assert!(write_trips(Vec::<u8>::new(), &trips, None).is_err());
+
+        assert!(geo_metadata(0).contains(r#""crs":null"#));
+        assert!(!geo_metadata(4326).contains("crs"));
+        assert!(geo_metadata(3857).contains(r#""code":3857"#));
+    }
+}
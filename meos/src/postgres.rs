@@ -0,0 +1,147 @@
+//! tokio-postgres support, enabled by the `postgres-types` feature
+//!
+//! Values are exchanged in the MobilityDB binary send/recv format, which is the
+//! meos WKB, so a `tgeompoint` column reads straight into a [TSeq] and a [TSeq]
+//! binds directly as a `tgeompoint` parameter.
+
+use bytes::BytesMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::span::{FloatSpan, IntSpan, TsTzSpan};
+use crate::stbox::STBox;
+use crate::tbox::TBox;
+use crate::tinst::TInst;
+use crate::tseq::TSeq;
+use crate::tset::TSet;
+use crate::{TemporalType, Wkb};
+
+type BoxError = Box<dyn std::error::Error + Sync + Send>;
+
+macro_rules! wkb_sql {
+    ($names:expr => $($t:ty),*) => {$(
+        impl ToSql for $t {
+            fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+                out.extend_from_slice(&self.to_wkb());
+                Ok(IsNull::No)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                $names.contains(&ty.name())
+            }
+
+            to_sql_checked!();
+        }
+
+        impl<'a> FromSql<'a> for $t {
+            fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+                Ok(<$t>::from_wkb(raw)?)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                $names.contains(&ty.name())
+            }
+        }
+    )*};
+}
+
+wkb_sql!(TemporalType::NAMES => TInst, TSeq, TSet);
+wkb_sql!(["stbox"] => STBox);
+wkb_sql!(["tbox"] => TBox);
+// MobilityDB before 1.1 calls the timestamp span a period
+wkb_sql!(["tstzspan", "period"] => TsTzSpan);
+wkb_sql!(["intspan"] => IntSpan);
+wkb_sql!(["floatspan"] => FloatSpan);
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, Kind, ToSql, Type};
+
+    use crate::prelude::*;
+    use crate::span::TsTzSpan;
+    use crate::stbox::STBox;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    /// extension types have no fixed oid, only the name is checked
+    fn ext_type(name: &str) -> Type {
+        Type::new(name.into(), 0, Kind::Simple, "public".into())
+    }
+
+    #[test]
+    fn test_temporal_roundtrip() {
+        let ty = ext_type("tgeompoint");
+        assert!(<TSeq as ToSql>::accepts(&ty));
+        assert!(!<TSeq as ToSql>::accepts(&ext_type("stbox")));
+
+        let seq =
+            TSeq::from_wkt("SRID=4326;[POINT(1 1)@2000-01-01, POINT(2 2)@2000-01-02]").unwrap();
+        let mut buf = BytesMut::new();
+        seq.to_sql_checked(&ty, &mut buf).unwrap();
+        let back = TSeq::from_sql(&ty, &buf).unwrap();
+        assert!(seq == back);
+        assert!(TInst::from_sql(&ty, &buf).is_err());
+    }
+
+    #[test]
+    fn test_box_roundtrip() {
+        let ty = ext_type("stbox");
+        let b = STBox::from_wkt("STBOX X((1,1),(2,2))").unwrap();
+        let mut buf = BytesMut::new();
+        b.to_sql_checked(&ty, &mut buf).unwrap();
+        assert_eq!(b, STBox::from_sql(&ty, &buf).unwrap());
+    }
+
+    #[test]
+    fn test_params_are_sync() {
+        fn param<T: ToSql + Sync>() {}
+        param::<TSeq>();
+        param::<STBox>();
+        param::<TsTzSpan>();
+    }
+
+    /// needs a Postgres with MobilityDB at `MOBILITYDB_URL`
+    #[tokio::test]
+    #[ignore]
+    async fn test_execute() {
+        let url = std::env::var("MOBILITYDB_URL").unwrap();
+        let (client, connection) = tokio_postgres::connect(&url, tokio_postgres::NoTls)
+            .await
+            .unwrap();
+        tokio::spawn(connection);
+
+        let seq = TSeq::from_wkt(
+            "SRID=4326;[POINT(1 1)@2000-01-01 00:00:00+00, POINT(2 2)@2000-01-02 00:00:00+00]",
+        )
+        .unwrap();
+        client
+            .execute("CREATE TEMP TABLE trips (trip tgeompoint)", &[])
+            .await
+            .unwrap();
+        client
+            .execute("INSERT INTO trips VALUES ($1)", &[&seq])
+            .await
+            .unwrap();
+        let row = client
+            .query_one("SELECT trip, stbox(trip) FROM trips", &[])
+            .await
+            .unwrap();
+        assert!(seq == row.get::<_, TSeq>(0));
+        let extent = STBox::from_wkt(
+            "SRID=4326;STBOX XT(((1,1),(2,2)),[2000-01-01 00:00:00+00, 2000-01-02 00:00:00+00])",
+        )
+        .unwrap();
+        assert_eq!(extent, row.get::<_, STBox>(1));
+    }
+}
@@ -1,5 +1,7 @@
 pub use crate::error::Error;
 pub use crate::temp::*;
 pub use crate::tinst::*;
+pub use crate::tpoint::*;
 pub use crate::tseq::*;
 pub use crate::tset::*;
+pub use crate::{Interp, TemporalType, Type, Wkb};
@@ -0,0 +1,207 @@
+//! Serde support, enabled by the `serde` feature
+//!
+//! Human readable formats get MF-JSON for temporals and WKT for boxes and spans,
+//! binary formats get WKB. The [mfjson] and [wkb] modules force a representation
+//! through `#[serde(with = "...")]`:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Trip {
+//!     mmsi: i64,
+//!     #[serde(with = "meos::serde::wkb")]
+//!     trip: TSeq,
+//! }
+//! ```
+
+use std::fmt::Formatter;
+
+use ::serde::de::{Error as _, Visitor};
+use ::serde::ser::Error as _;
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::span::{FloatSpan, IntSpan, TsTzSpan};
+use crate::stbox::STBox;
+use crate::tbox::TBox;
+use crate::temp::Temporal;
+use crate::tinst::TInst;
+use crate::tseq::TSeq;
+use crate::tset::TSet;
+use crate::Wkb;
+
+/// Temporals as MF-JSON objects
+pub mod mfjson {
+    use super::*;
+
+    pub fn serialize<T: Temporal, S: Serializer>(t: &T, s: S) -> Result<S::Ok, S::Error> {
+        let json = t.to_mf_json().map_err(S::Error::custom)?;
+        let v: serde_json::Value = serde_json::from_str(&json).map_err(S::Error::custom)?;
+        v.serialize(s)
+    }
+
+    pub fn deserialize<'de, T: Temporal, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        let v = serde_json::Value::deserialize(d)?;
+        T::from_mf_json(&v.to_string()).map_err(D::Error::custom)
+    }
+}
+
+/// Temporals, boxes and spans as WKB, hex encoded in human readable formats
+pub mod wkb {
+    use super::*;
+
+    pub fn serialize<T: Wkb, S: Serializer>(t: &T, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            let hex = t
+                .as_hex()
+                .ok_or_else(|| S::Error::custom("meos hex wkb failed"))?;
+            s.serialize_str(&hex)
+        } else {
+            s.serialize_bytes(&t.to_wkb())
+        }
+    }
+
+    pub fn deserialize<'de, T: Wkb, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        if d.is_human_readable() {
+            let hex = String::deserialize(d)?;
+            T::from_hex(&hex).map_err(D::Error::custom)
+        } else {
+            let bytes = d.deserialize_byte_buf(WkbVisitor)?;
+            T::from_wkb(&bytes).map_err(D::Error::custom)
+        }
+    }
+
+    struct WkbVisitor;
+
+    impl<'de> Visitor<'de> for WkbVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            f.write_str("wkb bytes")
+        }
+
+        fn visit_bytes<E: ::serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: ::serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: ::serde::de::SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(b) = seq.next_element()? {
+                v.push(b);
+            }
+            Ok(v)
+        }
+    }
+}
+
+macro_rules! temporal_serde {
+    ($($t:ty),*) => {$(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                if s.is_human_readable() {
+                    mfjson::serialize(self, s)
+                } else {
+                    wkb::serialize(self, s)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                if d.is_human_readable() {
+                    mfjson::deserialize(d)
+                } else {
+                    wkb::deserialize(d)
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! wkt_serde {
+    ($($t:ty),*) => {$(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                if s.is_human_readable() {
+                    s.serialize_str(&self.to_string())
+                } else {
+                    wkb::serialize(self, s)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                if d.is_human_readable() {
+                    let wkt = String::deserialize(d)?;
+                    <$t>::from_wkt(&wkt).map_err(D::Error::custom)
+                } else {
+                    wkb::deserialize(d)
+                }
+            }
+        }
+    )*};
+}
+
+temporal_serde!(TInst, TSeq, TSet);
+wkt_serde!(STBox, TBox, TsTzSpan, IntSpan, FloatSpan);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::stbox::STBox;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Trip {
+        id: i64,
+        trip: TSeq,
+        #[serde(with = "crate::serde::wkb")]
+        extent: STBox,
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        const WKT: &str = "[POINT(1 1)@2000-01-01, POINT(2 2)@2000-01-02]";
+        let trip = TSeq::from_wkt(WKT).unwrap();
+        let extent = STBox::from_wkt("STBOX X((1,1),(2,2))").unwrap();
+        let json = serde_json::to_string(&Trip {
+            id: 1,
+            trip,
+            extent,
+        })
+        .unwrap();
+        assert!(json.contains(r#""type":"MovingPoint""#), "{json}");
+
+        let back: Trip = serde_json::from_str(&json).unwrap();
+        assert_eq!(1, back.id);
+        assert!(TSeq::from_wkt(WKT).unwrap() == back.trip);
+        assert_eq!("STBOX X((1,1),(2,2))", back.extent.as_wkt(6).unwrap());
+    }
+
+    #[test]
+    fn test_box_wkt() {
+        let b = STBox::from_wkt("STBOX X((1,1),(2,2))").unwrap();
+        let json = serde_json::to_string(&b).unwrap();
+        assert_eq!(r#""STBOX X((1,1),(2,2))""#, json);
+        let back: STBox = serde_json::from_str(&json).unwrap();
+        assert_eq!(b, back);
+    }
+}
@@ -0,0 +1,268 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::ptr::NonNull;
+
+use libc::free;
+
+use meos_sys as ffi;
+use meos_sys::WKB_EXTENDED;
+
+use crate::error::Error;
+use crate::error::Error::MeosError;
+use crate::tz::{Interval, Timestamp};
+use crate::{take_bytes, take_hex, to_c_str, try_cstr_to_str, Wkb};
+
+pub(crate) trait SpanPtr {
+    fn ptr(&self) -> *mut ffi::Span;
+}
+
+/// Spans over numbers, the value dimension of a [TBox](crate::tbox::TBox)
+#[allow(private_bounds)]
+pub trait NumSpan: SpanPtr {}
+
+/// Ownership, comparison and WKB impls shared by the span types
+macro_rules! span_common {
+    ($($t:ident),*) => {$(
+        impl $t {
+            /// takes ownership of a meos allocated span
+            pub(crate) fn from_ptr(ptr: *mut ffi::Span) -> Result<Self, Error> {
+                // todo;; check the meos error
+                NonNull::new(ptr)
+                    .map(|ptr| Self { ptr })
+                    .ok_or(MeosError(-999))
+            }
+        }
+
+        impl Drop for $t {
+            fn drop(&mut self) {
+                unsafe {
+                    free(self.ptr.as_ptr().cast());
+                }
+            }
+        }
+
+        impl PartialEq<Self> for $t {
+            fn eq(&self, other: &Self) -> bool {
+                unsafe { ffi::span_eq(self.ptr(), other.ptr()) }
+            }
+        }
+
+        impl Eq for $t {}
+
+        impl PartialOrd<Self> for $t {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $t {
+            fn cmp(&self, other: &Self) -> Ordering {
+                unsafe {
+                    match ffi::span_cmp(self.ptr(), other.ptr()) {
+                        -1 => Ordering::Less,
+                        0 => Ordering::Equal,
+                        1 => Ordering::Greater,
+                        v => unreachable!("span_cmp returned {}", v),
+                    }
+                }
+            }
+        }
+
+        impl Wkb for $t {
+            fn to_wkb(&self) -> Vec<u8> {
+                let mut size = 0;
+                unsafe {
+                    let bytes = ffi::span_as_wkb(self.ptr(), WKB_EXTENDED as u8, &mut size);
+                    take_bytes(bytes, size)
+                }
+            }
+
+            fn from_wkb(wkb: &[u8]) -> Result<Self, Error> {
+                unsafe { Self::from_ptr(ffi::span_from_wkb(wkb.as_ptr(), wkb.len())) }
+            }
+
+            fn as_hex(&self) -> Option<String> {
+                let mut size = 0;
+                unsafe { take_hex(ffi::span_as_hexwkb(self.ptr(), WKB_EXTENDED as u8, &mut size)) }
+            }
+
+            fn from_hex(hex: &str) -> Result<Self, Error> {
+                let cstr = to_c_str(hex)?;
+                unsafe { Self::from_ptr(ffi::span_from_hexwkb(cstr.as_ptr())) }
+            }
+        }
+
+        impl Display for $t {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.as_wkt().map_err(|_| std::fmt::Error)?)
+            }
+        }
+
+        impl Debug for $t {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(self, f)
+            }
+        }
+    )*};
+}
+
+span_common!(TsTzSpan, IntSpan, FloatSpan);
+
+/// A span of timestamps, the meos `tstzspan`
+pub struct TsTzSpan {
+    ptr: NonNull<ffi::Span>,
+}
+
+impl TsTzSpan {
+    pub(crate) fn ptr(&self) -> *mut ffi::Span {
+        self.ptr.as_ptr()
+    }
+
+    pub fn new(
+        lower: impl Into<Timestamp>,
+        upper: impl Into<Timestamp>,
+        lower_inc: bool,
+        upper_inc: bool,
+    ) -> Result<Self, Error> {
+        let (lower, upper) = (lower.into().micros(), upper.into().micros());
+        unsafe { Self::from_ptr(ffi::period_make(lower, upper, lower_inc, upper_inc)) }
+    }
+
+    pub fn from_wkt(wkt: &str) -> Result<Self, Error> {
+        unsafe {
+            let cstr = to_c_str(wkt)?;
+            Self::from_ptr(ffi::period_in(cstr.as_ptr()))
+        }
+    }
+
+    pub fn as_wkt(&self) -> Result<String, Error> {
+        unsafe {
+            let cstr = ffi::period_out(self.ptr());
+            try_cstr_to_str(cstr)
+        }
+    }
+
+    pub fn lower(&self) -> Timestamp {
+        Timestamp::from_micros(unsafe { ffi::period_lower(self.ptr()) })
+    }
+
+    pub fn upper(&self) -> Timestamp {
+        Timestamp::from_micros(unsafe { ffi::period_upper(self.ptr()) })
+    }
+
+    /// the length of the span
+    pub fn duration(&self) -> Interval {
+        unsafe {
+            let i = ffi::period_duration(self.ptr());
+            let d = Interval::from(&*i);
+            free(i.cast());
+            d
+        }
+    }
+
+    pub fn lower_inc(&self) -> bool {
+        unsafe { ffi::span_lower_inc(self.ptr()) }
+    }
+
+    pub fn upper_inc(&self) -> bool {
+        unsafe { ffi::span_upper_inc(self.ptr()) }
+    }
+}
+
+/// A span of integers, the meos `intspan`
+pub struct IntSpan {
+    ptr: NonNull<ffi::Span>,
+}
+
+impl SpanPtr for IntSpan {
+    fn ptr(&self) -> *mut ffi::Span {
+        self.ptr.as_ptr()
+    }
+}
+
+impl NumSpan for IntSpan {}
+
+impl IntSpan {
+    pub fn new(lower: i32, upper: i32, lower_inc: bool, upper_inc: bool) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::intspan_make(lower, upper, lower_inc, upper_inc)) }
+    }
+
+    pub fn from_wkt(wkt: &str) -> Result<Self, Error> {
+        unsafe {
+            let cstr = to_c_str(wkt)?;
+            Self::from_ptr(ffi::intspan_in(cstr.as_ptr()))
+        }
+    }
+
+    pub fn as_wkt(&self) -> Result<String, Error> {
+        unsafe {
+            let cstr = ffi::intspan_out(self.ptr());
+            try_cstr_to_str(cstr)
+        }
+    }
+
+    pub fn lower(&self) -> i32 {
+        unsafe { ffi::intspan_lower(self.ptr()) }
+    }
+
+    pub fn upper(&self) -> i32 {
+        unsafe { ffi::intspan_upper(self.ptr()) }
+    }
+
+    pub fn lower_inc(&self) -> bool {
+        unsafe { ffi::span_lower_inc(self.ptr()) }
+    }
+
+    pub fn upper_inc(&self) -> bool {
+        unsafe { ffi::span_upper_inc(self.ptr()) }
+    }
+}
+
+/// A span of floats, the meos `floatspan`
+pub struct FloatSpan {
+    ptr: NonNull<ffi::Span>,
+}
+
+impl SpanPtr for FloatSpan {
+    fn ptr(&self) -> *mut ffi::Span {
+        self.ptr.as_ptr()
+    }
+}
+
+impl NumSpan for FloatSpan {}
+
+impl FloatSpan {
+    pub fn new(lower: f64, upper: f64, lower_inc: bool, upper_inc: bool) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::floatspan_make(lower, upper, lower_inc, upper_inc)) }
+    }
+
+    pub fn from_wkt(wkt: &str) -> Result<Self, Error> {
+        unsafe {
+            let cstr = to_c_str(wkt)?;
+            Self::from_ptr(ffi::floatspan_in(cstr.as_ptr()))
+        }
+    }
+
+    pub fn as_wkt(&self) -> Result<String, Error> {
+        unsafe {
+            let cstr = ffi::floatspan_out(self.ptr(), 15);
+            try_cstr_to_str(cstr)
+        }
+    }
+
+    pub fn lower(&self) -> f64 {
+        unsafe { ffi::floatspan_lower(self.ptr()) }
+    }
+
+    pub fn upper(&self) -> f64 {
+        unsafe { ffi::floatspan_upper(self.ptr()) }
+    }
+
+    pub fn lower_inc(&self) -> bool {
+        unsafe { ffi::span_lower_inc(self.ptr()) }
+    }
+
+    pub fn upper_inc(&self) -> bool {
+        unsafe { ffi::span_upper_inc(self.ptr()) }
+    }
+}
@@ -0,0 +1,188 @@
+//! sqlx support for Postgres, enabled by the `sqlx` feature
+//!
+//! Values are exchanged in the MobilityDB binary send/recv format, the meos
+//! WKB. A temporal binds with the type name of its base type, from
+//! [Encode::produces], and decodes from any of the MobilityDB temporal types.
+//! Its static [Type::type_info] can't know the base type and is only the
+//! `tgeompoint` fallback for values without one.
+
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+use ::sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use ::sqlx::{Decode, Encode, Type, TypeInfo};
+
+use crate::span::{FloatSpan, IntSpan, TsTzSpan};
+use crate::stbox::STBox;
+use crate::tbox::TBox;
+use crate::temp::Temporal;
+use crate::tinst::TInst;
+use crate::tseq::TSeq;
+use crate::tset::TSet;
+use crate::{TemporalType, Wkb};
+
+fn wkb_bytes(value: PgValueRef<'_>) -> Result<&[u8], BoxDynError> {
+    match value.format() {
+        PgValueFormat::Binary => value.as_bytes(),
+        PgValueFormat::Text => Err(
+            "unsupported decode of a MobilityDB type in a simple query; use a prepared query"
+                .into(),
+        ),
+    }
+}
+
+macro_rules! wkb_sqlx {
+    ($name:literal, $names:expr => $($t:ty),*) => {$(
+        impl Type<Postgres> for $t {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::with_name($name)
+            }
+
+            fn compatible(ty: &PgTypeInfo) -> bool {
+                $names.contains(&ty.name())
+            }
+        }
+
+        impl Encode<'_, Postgres> for $t {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                buf.extend_from_slice(&self.to_wkb());
+                Ok(IsNull::No)
+            }
+        }
+
+        impl<'r> Decode<'r, Postgres> for $t {
+            fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+                Ok(<$t>::from_wkb(wkb_bytes(value)?)?)
+            }
+        }
+    )*};
+}
+
+macro_rules! temporal_sqlx {
+    ($($t:ty),*) => {$(
+        impl Type<Postgres> for $t {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::with_name("tgeompoint")
+            }
+
+            fn compatible(ty: &PgTypeInfo) -> bool {
+                TemporalType::NAMES.contains(&ty.name())
+            }
+        }
+
+        impl Encode<'_, Postgres> for $t {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                buf.extend_from_slice(&self.to_wkb());
+                Ok(IsNull::No)
+            }
+
+            fn produces(&self) -> Option<PgTypeInfo> {
+                let ty = self.temporal_type().ok()?;
+                Some(PgTypeInfo::with_name(ty.name()))
+            }
+        }
+
+        impl<'r> Decode<'r, Postgres> for $t {
+            fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+                Ok(<$t>::from_wkb(wkb_bytes(value)?)?)
+            }
+        }
+    )*};
+}
+
+temporal_sqlx!(TInst, TSeq, TSet);
+wkb_sqlx!("stbox", ["stbox"] => STBox);
+wkb_sqlx!("tbox", ["tbox"] => TBox);
+// MobilityDB before 1.1 calls the timestamp span a period
+wkb_sqlx!("tstzspan", ["tstzspan", "period"] => TsTzSpan);
+wkb_sqlx!("intspan", ["intspan"] => IntSpan);
+wkb_sqlx!("floatspan", ["floatspan"] => FloatSpan);
+
+#[cfg(test)]
+mod tests {
+    use ::sqlx::encode::IsNull;
+    use ::sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, Postgres};
+    use ::sqlx::{Encode, Type, TypeInfo};
+
+    use crate::prelude::*;
+    use crate::span::TsTzSpan;
+    use crate::stbox::STBox;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    #[test]
+    fn test_type_info() {
+        assert_eq!("tgeompoint", <TSeq as Type<Postgres>>::type_info().name());
+        assert_eq!("stbox", <STBox as Type<Postgres>>::type_info().name());
+        assert!(<TSeq as Type<Postgres>>::compatible(
+            &PgTypeInfo::with_name("tfloat")
+        ));
+        assert!(!<TSeq as Type<Postgres>>::compatible(
+            &PgTypeInfo::with_name("stbox")
+        ));
+        assert!(<TsTzSpan as Type<Postgres>>::compatible(
+            &PgTypeInfo::with_name("period")
+        ));
+        assert!(!<STBox as Type<Postgres>>::compatible(
+            &PgTypeInfo::with_name("tbox")
+        ));
+    }
+
+    #[test]
+    fn test_encode() {
+        let seq = TSeq::from_wkt_as(
+            "[1.5@2000-01-01 00:00:00+00, 2.5@2000-01-02 00:00:00+00]",
+            TemporalType::TFloat,
+        )
+        .unwrap();
+        assert_eq!(Some("tfloat"), seq.produces().as_ref().map(|ty| ty.name()));
+        let flag = TInst::from_wkt_as("t@2000-01-01 00:00:00+00", TemporalType::TBool).unwrap();
+        assert_eq!(Some("tbool"), flag.produces().as_ref().map(|ty| ty.name()));
+
+        let mut buf = PgArgumentBuffer::default();
+        assert!(matches!(seq.encode_by_ref(&mut buf).unwrap(), IsNull::No));
+        // a PgValueRef can't be built without a connection, decode reads the same wkb
+        assert!(seq == TSeq::from_wkb(&buf).unwrap());
+
+        let b = STBox::from_wkt("STBOX X((1,1),(2,2))").unwrap();
+        let mut buf = PgArgumentBuffer::default();
+        b.encode_by_ref(&mut buf).unwrap();
+        assert_eq!(b, STBox::from_wkb(&buf).unwrap());
+    }
+
+    /// needs a Postgres with MobilityDB at `MOBILITYDB_URL`
+    #[tokio::test]
+    #[ignore]
+    async fn test_decode() {
+        use ::sqlx::{Connection, PgConnection, Row};
+
+        let url = std::env::var("MOBILITYDB_URL").unwrap();
+        let mut conn = PgConnection::connect(&url).await.unwrap();
+        let speed = TSeq::from_wkt_as(
+            "[1.5@2000-01-01 00:00:00+00, 2.5@2000-01-02 00:00:00+00]",
+            TemporalType::TFloat,
+        )
+        .unwrap();
+        let row = ::sqlx::query("SELECT $1::tfloat, $1::tstzspan")
+            .bind(&speed)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert!(speed == row.try_get::<TSeq, _>(0).unwrap());
+        assert_eq!(
+            TsTzSpan::from_wkt("[2000-01-01 00:00:00+00, 2000-01-02 00:00:00+00]").unwrap(),
+            row.try_get::<TsTzSpan, _>(1).unwrap()
+        );
+        assert!(row.try_get::<STBox, _>(0).is_err());
+    }
+}
@@ -1,17 +1,24 @@
 use std::cmp::Ordering;
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::ptr::NonNull;
 
 use libc::free;
 
 use meos_sys as ffi;
 use meos_sys::{
-    contains_stbox_tpoint, overlaps_stbox_stbox, same_stbox_stbox, stbox_cmp, stbox_eq, stbox_out,
+    contained_stbox_tpoint, contains_stbox_stbox, contains_stbox_tpoint, overlaps_stbox_stbox,
+    same_stbox_stbox, stbox_cmp, stbox_eq, stbox_out, WKB_EXTENDED,
 };
 
 use crate::error::Error;
 use crate::error::Error::MeosError;
-use crate::{to_c_str, try_cstr_to_str};
+use crate::span::TsTzSpan;
+use crate::temp::Temporal;
+use crate::tpoint::ensure_point;
+use crate::tz::{Timestamp, ToInterval};
+use crate::{
+    geom_from_wkt, take_bytes, take_hex, to_c_str, try_cstr_to_str, TPtrCtr, Wkb, DEFAULT_PRECISION,
+};
 
 pub struct STBox {
     ptr: NonNull<ffi::STBox>,
@@ -52,17 +59,79 @@ impl Ord for STBox {
     }
 }
 
+impl Wkb for STBox {
+    fn to_wkb(&self) -> Vec<u8> {
+        let mut size = 0;
+        unsafe {
+            let bytes = ffi::stbox_as_wkb(self.ptr(), WKB_EXTENDED as u8, &mut size);
+            take_bytes(bytes, size)
+        }
+    }
+
+    fn from_wkb(wkb: &[u8]) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::stbox_from_wkb(wkb.as_ptr(), wkb.len())) }
+    }
+
+    fn as_hex(&self) -> Option<String> {
+        let mut size = 0;
+        unsafe {
+            take_hex(ffi::stbox_as_hexwkb(
+                self.ptr(),
+                WKB_EXTENDED as u8,
+                &mut size,
+            ))
+        }
+    }
+
+    fn from_hex(hex: &str) -> Result<Self, Error> {
+        let cstr = to_c_str(hex)?;
+        unsafe { Self::from_ptr(ffi::stbox_from_hexwkb(cstr.as_ptr())) }
+    }
+}
+
 impl Debug for STBox {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.as_wkt())
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for STBox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .as_ewkt(DEFAULT_PRECISION)
+                .map_err(|_| std::fmt::Error)?,
+        )
     }
 }
 
 impl STBox {
-    pub fn as_wkt(&self) -> String {
+    /// takes ownership of a meos allocated box
+    pub(crate) fn from_ptr(ptr: *mut ffi::STBox) -> Result<Self, Error> {
+        // todo;; check the meos error
+        NonNull::new(ptr)
+            .map(|ptr| Self { ptr })
+            .ok_or(MeosError(-999))
+    }
+
+    pub(crate) fn ptr(&self) -> *mut ffi::STBox {
+        self.ptr.as_ptr()
+    }
+
+    /// the WKT of the box with at most `precision` decimal digits
+    pub fn as_wkt(&self, precision: i32) -> Result<String, Error> {
+        let ewkt = self.as_ewkt(precision)?;
+        Ok(match ewkt.split_once(';') {
+            Some((srid, wkt)) if srid.starts_with("SRID=") => wkt.to_owned(),
+            _ => ewkt,
+        })
+    }
+
+    /// the WKT prefixed by the SRID when there is one, as read by [STBox::from_wkt]
+    pub fn as_ewkt(&self, precision: i32) -> Result<String, Error> {
         unsafe {
-            let cstr = stbox_out(self.ptr.as_ptr().cast(), 6);
-            try_cstr_to_str(cstr).expect("box as_wkt")
+            let cstr = stbox_out(self.ptr.as_ptr().cast(), precision);
+            try_cstr_to_str(cstr)
         }
     }
 
@@ -80,8 +149,58 @@ impl STBox {
         }
     }
 
+    /// the bounding box of a geometry given as (E)WKT
+    pub fn from_geometry(wkt: &str) -> Result<Self, Error> {
+        let gs = geom_from_wkt(wkt)?;
+        unsafe {
+            let b = ffi::geo_to_stbox(gs.as_ptr());
+            free(gs.as_ptr().cast());
+            Self::from_ptr(b)
+        }
+    }
+
+    pub fn from_timestamp(t: impl Into<Timestamp>) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::timestamp_to_stbox(t.into().micros())) }
+    }
+
+    pub fn from_span(span: &TsTzSpan) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::period_to_stbox(span.ptr())) }
+    }
+
+    /// the box of a geometry given as (E)WKT at a timestamp
+    pub fn from_geometry_timestamp(wkt: &str, t: impl Into<Timestamp>) -> Result<Self, Error> {
+        let gs = geom_from_wkt(wkt)?;
+        unsafe {
+            let b = ffi::geo_timestamp_to_stbox(gs.as_ptr(), t.into().micros());
+            free(gs.as_ptr().cast());
+            Self::from_ptr(b)
+        }
+    }
+
+    /// the box of a geometry given as (E)WKT over a span of time
+    pub fn from_geometry_span(wkt: &str, span: &TsTzSpan) -> Result<Self, Error> {
+        let gs = geom_from_wkt(wkt)?;
+        unsafe {
+            let b = ffi::geo_period_to_stbox(gs.as_ptr(), span.ptr());
+            free(gs.as_ptr().cast());
+            Self::from_ptr(b)
+        }
+    }
+
     pub fn contains(&self, other: &Self) -> bool {
-        unsafe { contains_stbox_tpoint(self.ptr.as_ptr().cast(), other.ptr.as_ptr().cast()) }
+        unsafe { contains_stbox_stbox(self.ptr.as_ptr(), other.ptr.as_ptr()) }
+    }
+
+    /// does this box contain the bounding box of a temporal point
+    pub fn contains_temporal(&self, t: &impl Temporal) -> Result<bool, Error> {
+        ensure_point(t)?;
+        Ok(unsafe { contains_stbox_tpoint(self.ptr.as_ptr(), t.ptr()) })
+    }
+
+    /// is this box contained in the bounding box of a temporal point
+    pub fn contained_in(&self, t: &impl Temporal) -> Result<bool, Error> {
+        ensure_point(t)?;
+        Ok(unsafe { contained_stbox_tpoint(self.ptr.as_ptr(), t.ptr()) })
     }
 
     pub fn overlaps(&self, other: &Self) -> bool {
@@ -91,11 +210,273 @@ impl STBox {
     pub fn same(&self, other: &Self) -> bool {
         unsafe { same_stbox_stbox(self.ptr.as_ptr(), other.ptr.as_ptr()) }
     }
+
+    pub fn xmin(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::stbox_xmin(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn ymin(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::stbox_ymin(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn zmin(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::stbox_zmin(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn xmax(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::stbox_xmax(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn ymax(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::stbox_ymax(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn zmax(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::stbox_zmax(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn tmin(&self) -> Option<Timestamp> {
+        let mut v = 0;
+        unsafe { ffi::stbox_tmin(self.ptr(), &mut v) }.then_some(Timestamp::from_micros(v))
+    }
+
+    pub fn tmax(&self) -> Option<Timestamp> {
+        let mut v = 0;
+        unsafe { ffi::stbox_tmax(self.ptr(), &mut v) }.then_some(Timestamp::from_micros(v))
+    }
+
+    pub fn srid(&self) -> i32 {
+        unsafe { ffi::stbox_srid(self.ptr()) }
+    }
+
+    pub fn has_x(&self) -> bool {
+        unsafe { ffi::stbox_hasx(self.ptr()) }
+    }
+
+    pub fn has_z(&self) -> bool {
+        unsafe { ffi::stbox_hasz(self.ptr()) }
+    }
+
+    pub fn has_t(&self) -> bool {
+        unsafe { ffi::stbox_hast(self.ptr()) }
+    }
+
+    pub fn is_geodetic(&self) -> bool {
+        unsafe { ffi::stbox_isgeodetic(self.ptr()) }
+    }
+
+    /// the box expanded by `d` in every spatial dimension
+    pub fn expand_space(&self, d: f64) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::stbox_expand_space(self.ptr(), d)) }
+    }
+
+    /// the box expanded by `d` on both sides of its time dimension
+    pub fn expand_time<D: ToInterval>(&self, d: D) -> Result<Self, Error> {
+        let d = d.to_interval()?;
+        unsafe { Self::from_ptr(ffi::stbox_expand_time(self.ptr(), &d)) }
+    }
+
+    /// the smallest box containing both boxes
+    pub fn union(&self, other: &Self) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::union_stbox_stbox(self.ptr(), other.ptr(), false)) }
+    }
+
+    /// the common part of both boxes, `None` when they do not intersect
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        unsafe { Self::from_ptr(ffi::intersection_stbox_stbox(self.ptr(), other.ptr())).ok() }
+    }
+
+    /// the box with its coordinates rounded to `maxdd` decimal digits
+    pub fn round(&self, maxdd: i32) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::stbox_round(self.ptr(), maxdd)) }
+    }
+
+    /// the box with its SRID replaced, coordinates are unchanged
+    pub fn set_srid(&self, srid: i32) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::stbox_set_srid(self.ptr(), srid)) }
+    }
+
+    /// the box with its coordinates transformed to `srid`
+    pub fn transform(&self, srid: i32) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::stbox_transform(self.ptr(), srid)) }
+    }
+
+    /// the box with its coordinates transformed by a PROJ pipeline
+    ///
+    /// The result is tagged with `srid`, the pipeline is run in reverse when
+    /// `forward` is false.
+    pub fn transform_pipeline(
+        &self,
+        pipeline: &str,
+        srid: i32,
+        forward: bool,
+    ) -> Result<Self, Error> {
+        let cstr = to_c_str(pipeline)?;
+        unsafe {
+            Self::from_ptr(ffi::stbox_transform_pipeline(
+                self.ptr(),
+                cstr.as_ptr(),
+                srid,
+                forward,
+            ))
+        }
+    }
+
+    /// is this box strictly left of the other
+    pub fn left(&self, other: &Self) -> bool {
+        unsafe { ffi::left_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend to the right of the other
+    pub fn overleft(&self, other: &Self) -> bool {
+        unsafe { ffi::overleft_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly right of the other
+    pub fn right(&self, other: &Self) -> bool {
+        unsafe { ffi::right_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend to the left of the other
+    pub fn overright(&self, other: &Self) -> bool {
+        unsafe { ffi::overright_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly below the other
+    pub fn below(&self, other: &Self) -> bool {
+        unsafe { ffi::below_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend above the other
+    pub fn overbelow(&self, other: &Self) -> bool {
+        unsafe { ffi::overbelow_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly above the other
+    pub fn above(&self, other: &Self) -> bool {
+        unsafe { ffi::above_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend below the other
+    pub fn overabove(&self, other: &Self) -> bool {
+        unsafe { ffi::overabove_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly in front of the other
+    pub fn front(&self, other: &Self) -> bool {
+        unsafe { ffi::front_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend to the back of the other
+    pub fn overfront(&self, other: &Self) -> bool {
+        unsafe { ffi::overfront_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly behind the other
+    pub fn back(&self, other: &Self) -> bool {
+        unsafe { ffi::back_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend to the front of the other
+    pub fn overback(&self, other: &Self) -> bool {
+        unsafe { ffi::overback_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly before the other
+    pub fn before(&self, other: &Self) -> bool {
+        unsafe { ffi::before_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend after the other
+    pub fn overbefore(&self, other: &Self) -> bool {
+        unsafe { ffi::overbefore_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly after the other
+    pub fn after(&self, other: &Self) -> bool {
+        unsafe { ffi::after_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend before the other
+    pub fn overafter(&self, other: &Self) -> bool {
+        unsafe { ffi::overafter_stbox_stbox(self.ptr(), other.ptr()) }
+    }
+
+    /// grid cells of the given sizes covering this box
+    ///
+    /// Cells are aligned on the spatial origin `sorigin` in the box SRID and on
+    /// the time `torigin`. The `zsize` is ignored when the box has no Z dimension.
+    pub fn tiles<D: ToInterval>(
+        &self,
+        xsize: f64,
+        ysize: f64,
+        zsize: f64,
+        duration: D,
+        sorigin: (f64, f64, f64),
+        torigin: impl Into<Timestamp>,
+    ) -> Result<Vec<STBox>, Error> {
+        let duration = duration.to_interval()?;
+        let (srid, hasz) = unsafe { (ffi::stbox_srid(self.ptr()), ffi::stbox_hasz(self.ptr())) };
+        let sorigin = space_origin(srid, hasz, sorigin)?;
+        let mut count = 0;
+        unsafe {
+            let tiles = ffi::stbox_tile_list(
+                self.ptr(),
+                xsize,
+                ysize,
+                zsize,
+                &duration,
+                sorigin.as_ptr(),
+                torigin.into().micros(),
+                &mut count,
+            );
+            free(sorigin.as_ptr().cast());
+            if tiles.is_null() {
+                // todo;; check the meos error
+                return Err(MeosError(-999));
+            }
+            Ok(stboxes_from(tiles, count))
+        }
+    }
+}
+
+/// takes ownership of a meos allocated array of boxes
+pub(crate) unsafe fn stboxes_from(boxes: *mut ffi::STBox, count: i32) -> Vec<STBox> {
+    if boxes.is_null() {
+        return vec![];
+    }
+    let res = (0..count as usize)
+        .filter_map(|i| STBox::from_ptr(ffi::stbox_copy(boxes.add(i))).ok())
+        .collect();
+    free(boxes.cast());
+    res
+}
+
+/// the origin point of a spatial grid, owned by the caller
+pub(crate) fn space_origin(
+    srid: i32,
+    hasz: bool,
+    (x, y, z): (f64, f64, f64),
+) -> Result<NonNull<ffi::GSERIALIZED>, Error> {
+    let wkt = if hasz {
+        format!("SRID={srid};POINT Z({x} {y} {z})")
+    } else {
+        format!("SRID={srid};POINT({x} {y})")
+    };
+    geom_from_wkt(&wkt)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::prelude::*;
     use crate::stbox::STBox;
+    use crate::tz::Timestamp;
     use crate::{finalize, init};
 
     #[cfg(test)]
@@ -114,12 +495,158 @@ mod tests {
     fn test_tbox_wkt() {
         let a = "STBOX X((1.0, 2.0), (3.0, 4.0))";
         let o = "STBOX X((1,2),(3,4))";
-        let b = STBox::from_wkt(a).unwrap().as_wkt();
+        let b = STBox::from_wkt(a).unwrap().as_wkt(6).unwrap();
         assert_eq!(o, b);
 
         let a = "STBOX Z((1.0, 2.0, 3.0), (4.0, 5.0, 6.0))";
         let o = "STBOX Z((1,2,3),(4,5,6))";
-        let b = STBox::from_wkt(a).unwrap().as_wkt();
+        let b = STBox::from_wkt(a).unwrap().as_wkt(6).unwrap();
         assert_eq!(o, b);
     }
+
+    #[test]
+    fn test_stbox_accessors() {
+        let b = STBox::from_wkt("STBOX X((1.0, 2.0), (3.0, 4.0))").unwrap();
+        assert_eq!(Some(1.0), b.xmin());
+        assert_eq!(Some(2.0), b.ymin());
+        assert_eq!(Some(3.0), b.xmax());
+        assert_eq!(Some(4.0), b.ymax());
+        assert!(b.has_x());
+        assert!(!b.has_z());
+        assert!(!b.has_t());
+        assert!(!b.is_geodetic());
+        assert_eq!(None, b.tmin());
+
+        let b =
+            STBox::from_wkt("STBOX T([2000-01-01 00:00:00+00, 2000-01-02 00:00:00+00])").unwrap();
+        assert!(!b.has_x());
+        assert_eq!(Some(Timestamp::from_micros(0)), b.tmin());
+        assert_eq!(Some(Timestamp::from_micros(86_400_000_000)), b.tmax());
+    }
+
+    #[test]
+    fn test_stbox_from_geometry() {
+        let b = STBox::from_geometry("POINT(1 2)").unwrap();
+        assert_eq!("STBOX X((1,2),(1,2))", b.as_wkt(6).unwrap());
+
+        let b = STBox::from_geometry("LINESTRING(1 2, 3 4)").unwrap();
+        assert_eq!("STBOX X((1,2),(3,4))", b.as_wkt(6).unwrap());
+    }
+
+    #[test]
+    fn test_stbox_set_ops() {
+        let a = STBox::from_wkt("STBOX X((1,2),(3,4))").unwrap();
+        let b = STBox::from_wkt("STBOX X((2,3),(5,6))").unwrap();
+        assert_eq!(
+            "STBOX X((1,2),(5,6))",
+            a.union(&b).unwrap().as_wkt(6).unwrap()
+        );
+        assert_eq!(
+            "STBOX X((2,3),(3,4))",
+            a.intersection(&b).unwrap().as_wkt(6).unwrap()
+        );
+
+        let c = STBox::from_wkt("STBOX X((10,10),(11,11))").unwrap();
+        assert!(a.intersection(&c).is_none());
+
+        assert_eq!(
+            "STBOX X((0,1),(4,5))",
+            a.expand_space(1.0).unwrap().as_wkt(6).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stbox_round() {
+        let a = STBox::from_wkt("STBOX X((1.123,2.456),(3.789,4.5))").unwrap();
+        assert_eq!(
+            "STBOX X((1.1,2.5),(3.8,4.5))",
+            a.round(1).unwrap().as_wkt(6).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stbox_position() {
+        let a = STBox::from_wkt("STBOX X((1,1),(2,2))").unwrap();
+        let b = STBox::from_wkt("STBOX X((3,3),(4,4))").unwrap();
+        assert!(a.left(&b));
+        assert!(a.overleft(&b));
+        assert!(!a.right(&b));
+        assert!(a.below(&b));
+        assert!(!a.above(&b));
+        assert!(b.right(&a));
+        assert!(b.above(&a));
+    }
+
+    #[test]
+    fn test_stbox_contains() {
+        let a = STBox::from_wkt("STBOX X((1,1),(4,4))").unwrap();
+        let b = STBox::from_wkt("STBOX X((2,2),(3,3))").unwrap();
+        assert!(a.contains(&b));
+        assert!(!b.contains(&a));
+
+        let c = STBox::from_wkt("STBOX X((3,3),(5,5))").unwrap();
+        assert!(!a.contains(&c));
+    }
+
+    #[test]
+    fn test_stbox_contains_temporal() {
+        let t = TSeq::from_wkt("[POINT(1 1)@2000-01-01, POINT(2 2)@2000-01-02]").unwrap();
+
+        let a = STBox::from_wkt("STBOX XT(((0,0),(4,4)),[2000-01-01, 2000-01-03])").unwrap();
+        assert!(a.contains_temporal(&t).unwrap());
+        assert!(!a.contained_in(&t).unwrap());
+
+        let b = STBox::from_wkt("STBOX XT(((1,1),(1.5,1.5)),[2000-01-01, 2000-01-02])").unwrap();
+        assert!(!b.contains_temporal(&t).unwrap());
+        assert!(b.contained_in(&t).unwrap());
+
+        let speed = TSeq::from_wkt_as("[1@2000-01-01 00:00:00+00]", TemporalType::TFloat).unwrap();
+        assert!(matches!(
+            a.contains_temporal(&speed),
+            Err(Error::WrongTemporalType)
+        ));
+        assert!(a.contained_in(&speed).is_err());
+    }
+
+    #[test]
+    fn test_stbox_tiles() {
+        let day = std::time::Duration::from_secs(86_400);
+        let b = STBox::from_wkt(
+            "STBOX XT(((1,1),(9,9)),[2000-01-01 01:00:00+00, 2000-01-01 23:00:00+00])",
+        )
+        .unwrap();
+        let origin = Timestamp::from_micros(0);
+        let tiles = b
+            .tiles(5.0, 5.0, 0.0, day, (0.0, 0.0, 0.0), origin)
+            .unwrap();
+        assert_eq!(4, tiles.len());
+        assert_eq!(Some(0.0), tiles[0].xmin());
+        assert_eq!(Some(origin), tiles[0].tmin());
+
+        let shifted = b
+            .tiles(5.0, 5.0, 0.0, day, (2.5, 2.5, 0.0), origin)
+            .unwrap();
+        assert_eq!(9, shifted.len());
+        assert!(b
+            .tiles(
+                5.0,
+                5.0,
+                0.0,
+                std::time::Duration::MAX,
+                (0.0, 0.0, 0.0),
+                origin
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_stbox_srid() {
+        let a = STBox::from_wkt("SRID=4326;STBOX X((1,2),(3,4))").unwrap();
+        assert_eq!(4326, a.srid());
+        assert_eq!(3857, a.set_srid(3857).unwrap().srid());
+        assert_eq!(32618, a.transform(32618).unwrap().srid());
+        assert_eq!("STBOX X((1,2),(3,4))", a.as_wkt(6).unwrap());
+        assert_eq!("SRID=4326;STBOX X((1,2),(3,4))", a.as_ewkt(6).unwrap());
+        assert_eq!("SRID=4326;STBOX X((1,2),(3,4))", a.to_string());
+    }
 }
@@ -1,18 +1,21 @@
 use std::cmp::Ordering;
-use std::fmt::{Debug, Formatter};
-use std::ptr::NonNull;
+use std::fmt::{Debug, Display, Formatter};
+use std::ptr::{null, NonNull};
 
 use libc::free;
 
 use meos_sys as ffi;
 use meos_sys::{
-    contains_tbox_tbox, int_to_tbox, overlaps_tbox_tbox, same_tbox_tbox, tbox_cmp, tbox_eq,
-    tbox_out,
+    contained_tbox_tnumber, contains_tbox_tbox, contains_tbox_tnumber, int_to_tbox,
+    overlaps_tbox_tbox, same_tbox_tbox, tbox_cmp, tbox_eq, tbox_out, WKB_EXTENDED,
 };
 
 use crate::error::Error;
 use crate::error::Error::MeosError;
-use crate::{to_c_str, try_cstr_to_str};
+use crate::span::{NumSpan, SpanPtr, TsTzSpan};
+use crate::temp::{ensure_number, Temporal};
+use crate::tz::{Timestamp, ToInterval};
+use crate::{take_bytes, take_hex, to_c_str, try_cstr_to_str, TPtrCtr, Wkb, DEFAULT_PRECISION};
 
 // todo;; TBox trait?
 
@@ -23,7 +26,6 @@ pub struct TBox {
 impl Drop for TBox {
     fn drop(&mut self) {
         unsafe {
-            println!("dropped");
             free(self.ptr.as_ptr().cast());
         }
     }
@@ -56,20 +58,83 @@ impl Ord for TBox {
     }
 }
 
+impl Wkb for TBox {
+    fn to_wkb(&self) -> Vec<u8> {
+        let mut size = 0;
+        unsafe {
+            let bytes = ffi::tbox_as_wkb(self.ptr(), WKB_EXTENDED as u8, &mut size);
+            take_bytes(bytes, size)
+        }
+    }
+
+    fn from_wkb(wkb: &[u8]) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::tbox_from_wkb(wkb.as_ptr(), wkb.len())) }
+    }
+
+    fn as_hex(&self) -> Option<String> {
+        let mut size = 0;
+        unsafe {
+            take_hex(ffi::tbox_as_hexwkb(
+                self.ptr(),
+                WKB_EXTENDED as u8,
+                &mut size,
+            ))
+        }
+    }
+
+    fn from_hex(hex: &str) -> Result<Self, Error> {
+        let cstr = to_c_str(hex)?;
+        unsafe { Self::from_ptr(ffi::tbox_from_hexwkb(cstr.as_ptr())) }
+    }
+}
+
 impl Debug for TBox {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.as_wkt())
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for TBox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .as_wkt(DEFAULT_PRECISION)
+                .map_err(|_| std::fmt::Error)?,
+        )
     }
 }
 
 impl TBox {
-    pub fn as_wkt(&self) -> String {
+    /// takes ownership of a meos allocated box
+    pub(crate) fn from_ptr(ptr: *mut ffi::TBox) -> Result<Self, Error> {
+        // todo;; check the meos error
+        NonNull::new(ptr)
+            .map(|ptr| Self { ptr })
+            .ok_or(MeosError(-999))
+    }
+
+    pub(crate) fn ptr(&self) -> *mut ffi::TBox {
+        self.ptr.as_ptr()
+    }
+
+    /// whether the value span is an integer span
+    fn is_int(&self) -> bool {
+        unsafe { (*self.ptr()).span.basetype == ffi::meosType_T_INT4 as u8 }
+    }
+
+    /// the WKT of the box with at most `precision` decimal digits
+    pub fn as_wkt(&self, precision: i32) -> Result<String, Error> {
         unsafe {
-            let cstr = tbox_out(self.ptr.as_ptr().cast(), 6);
-            try_cstr_to_str(cstr).expect("box as_wkt")
+            let cstr = tbox_out(self.ptr.as_ptr().cast(), precision);
+            try_cstr_to_str(cstr)
         }
     }
 
+    /// the same as [TBox::as_wkt], value boxes have no SRID
+    pub fn as_ewkt(&self, precision: i32) -> Result<String, Error> {
+        self.as_wkt(precision)
+    }
+
     pub fn from_wkt(wkt: &str) -> Result<Self, Error> {
         unsafe {
             let cstr = to_c_str(wkt)?;
@@ -84,23 +149,69 @@ impl TBox {
         }
     }
 
-    pub fn from_int(i: i32) -> Self {
-        unsafe {
-            let ptr = int_to_tbox(i);
-            if ptr.is_null() {
-                // todo;; check the meos error
-                panic!("meos internal error")
-            }
-            Self {
-                ptr: NonNull::new(ptr).unwrap().cast(),
-            }
-        }
+    pub fn from_int(i: i32) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(int_to_tbox(i)) }
+    }
+
+    pub fn from_float(f: f64) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::float_to_tbox(f)) }
+    }
+
+    pub fn from_value_span<S: NumSpan>(span: &S) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::span_to_tbox(span.ptr())) }
+    }
+
+    pub fn from_timestamp(t: impl Into<Timestamp>) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::timestamp_to_tbox(t.into().micros())) }
+    }
+
+    pub fn from_time_span(span: &TsTzSpan) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::period_to_tbox(span.ptr())) }
+    }
+
+    pub fn from_int_timestamp(i: i32, t: impl Into<Timestamp>) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::int_timestamp_to_tbox(i, t.into().micros())) }
+    }
+
+    pub fn from_float_timestamp(f: f64, t: impl Into<Timestamp>) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::float_timestamp_to_tbox(f, t.into().micros())) }
+    }
+
+    pub fn from_int_time_span(i: i32, span: &TsTzSpan) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::int_period_to_tbox(i, span.ptr())) }
+    }
+
+    pub fn from_float_time_span(f: f64, span: &TsTzSpan) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::float_period_to_tbox(f, span.ptr())) }
+    }
+
+    pub fn from_value_span_timestamp<S: NumSpan>(
+        span: &S,
+        t: impl Into<Timestamp>,
+    ) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::span_timestamp_to_tbox(span.ptr(), t.into().micros())) }
+    }
+
+    pub fn from_value_span_time_span<S: NumSpan>(span: &S, time: &TsTzSpan) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::tbox_make(span.ptr(), time.ptr())) }
     }
 
     pub fn contains(&self, other: &Self) -> bool {
         unsafe { contains_tbox_tbox(self.ptr.as_ptr(), other.ptr.as_ptr()) }
     }
 
+    /// does this box contain the bounding box of a temporal number
+    pub fn contains_temporal(&self, t: &impl Temporal) -> Result<bool, Error> {
+        ensure_number(t)?;
+        Ok(unsafe { contains_tbox_tnumber(self.ptr.as_ptr(), t.ptr()) })
+    }
+
+    /// is this box contained in the bounding box of a temporal number
+    pub fn contained_in(&self, t: &impl Temporal) -> Result<bool, Error> {
+        ensure_number(t)?;
+        Ok(unsafe { contained_tbox_tnumber(self.ptr.as_ptr(), t.ptr()) })
+    }
+
     pub fn overlaps(&self, other: &Self) -> bool {
         unsafe { overlaps_tbox_tbox(self.ptr.as_ptr(), other.ptr.as_ptr()) }
     }
@@ -108,11 +219,201 @@ impl TBox {
     pub fn same(&self, other: &Self) -> bool {
         unsafe { same_tbox_tbox(self.ptr.as_ptr(), other.ptr.as_ptr()) }
     }
+
+    pub fn xmin(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::tbox_xmin(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn xmax(&self) -> Option<f64> {
+        let mut v = 0.0;
+        unsafe { ffi::tbox_xmax(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn xmin_inc(&self) -> Option<bool> {
+        let mut v = false;
+        unsafe { ffi::tbox_xmin_inc(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn xmax_inc(&self) -> Option<bool> {
+        let mut v = false;
+        unsafe { ffi::tbox_xmax_inc(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn tmin(&self) -> Option<Timestamp> {
+        let mut v = 0;
+        unsafe { ffi::tbox_tmin(self.ptr(), &mut v) }.then_some(Timestamp::from_micros(v))
+    }
+
+    pub fn tmax(&self) -> Option<Timestamp> {
+        let mut v = 0;
+        unsafe { ffi::tbox_tmax(self.ptr(), &mut v) }.then_some(Timestamp::from_micros(v))
+    }
+
+    pub fn tmin_inc(&self) -> Option<bool> {
+        let mut v = false;
+        unsafe { ffi::tbox_tmin_inc(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn tmax_inc(&self) -> Option<bool> {
+        let mut v = false;
+        unsafe { ffi::tbox_tmax_inc(self.ptr(), &mut v) }.then_some(v)
+    }
+
+    pub fn has_x(&self) -> bool {
+        unsafe { ffi::tbox_hasx(self.ptr()) }
+    }
+
+    pub fn has_t(&self) -> bool {
+        unsafe { ffi::tbox_hast(self.ptr()) }
+    }
+
+    /// the box expanded by `d` on both sides of its value dimension
+    pub fn expand_value(&self, d: f64) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::tbox_expand_value(self.ptr(), d)) }
+    }
+
+    /// the box expanded by `d` on both sides of its time dimension
+    pub fn expand_time<D: ToInterval>(&self, d: D) -> Result<Self, Error> {
+        let d = d.to_interval()?;
+        unsafe { Self::from_ptr(ffi::tbox_expand_time(self.ptr(), &d)) }
+    }
+
+    /// the smallest box containing both boxes
+    pub fn union(&self, other: &Self) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::union_tbox_tbox(self.ptr(), other.ptr(), false)) }
+    }
+
+    /// the common part of both boxes, `None` when they do not intersect
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        unsafe { Self::from_ptr(ffi::intersection_tbox_tbox(self.ptr(), other.ptr())).ok() }
+    }
+
+    /// the box with its value span shifted by `shift` and/or scaled to `width`
+    ///
+    /// The `shift` and `width` are truncated for integer boxes.
+    pub fn shift_scale_value(&self, shift: Option<f64>, width: Option<f64>) -> Result<Self, Error> {
+        let (hasshift, haswidth) = (shift.is_some(), width.is_some());
+        let (shift, width) = (shift.unwrap_or_default(), width.unwrap_or_default());
+        unsafe {
+            let b = if self.is_int() {
+                ffi::tbox_shift_scale_int(
+                    self.ptr(),
+                    shift as i32,
+                    width as i32,
+                    hasshift,
+                    haswidth,
+                )
+            } else {
+                ffi::tbox_shift_scale_float(self.ptr(), shift, width, hasshift, haswidth)
+            };
+            Self::from_ptr(b)
+        }
+    }
+
+    /// the box with its time span shifted by `shift` and/or scaled to `duration`
+    pub fn shift_scale_time<D: ToInterval>(
+        &self,
+        shift: Option<D>,
+        duration: Option<D>,
+    ) -> Result<Self, Error> {
+        let shift = shift.map(|d| d.to_interval()).transpose()?;
+        let duration = duration.map(|d| d.to_interval()).transpose()?;
+        unsafe {
+            Self::from_ptr(ffi::tbox_shift_scale_time(
+                self.ptr(),
+                shift.as_ref().map_or(null(), |i| i as *const _),
+                duration.as_ref().map_or(null(), |i| i as *const _),
+            ))
+        }
+    }
+
+    /// is this box strictly left of the other
+    pub fn left(&self, other: &Self) -> bool {
+        unsafe { ffi::left_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend to the right of the other
+    pub fn overleft(&self, other: &Self) -> bool {
+        unsafe { ffi::overleft_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly right of the other
+    pub fn right(&self, other: &Self) -> bool {
+        unsafe { ffi::right_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend to the left of the other
+    pub fn overright(&self, other: &Self) -> bool {
+        unsafe { ffi::overright_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly before the other
+    pub fn before(&self, other: &Self) -> bool {
+        unsafe { ffi::before_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend after the other
+    pub fn overbefore(&self, other: &Self) -> bool {
+        unsafe { ffi::overbefore_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// is this box strictly after the other
+    pub fn after(&self, other: &Self) -> bool {
+        unsafe { ffi::after_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// does this box not extend before the other
+    pub fn overafter(&self, other: &Self) -> bool {
+        unsafe { ffi::overafter_tbox_tbox(self.ptr(), other.ptr()) }
+    }
+
+    /// grid cells of `vsize` values by `duration` covering this box
+    ///
+    /// Cells are aligned on the value `vorigin` and the time `torigin`, the
+    /// `vsize` and `vorigin` are truncated for integer boxes.
+    pub fn tiles<D: ToInterval>(
+        &self,
+        vsize: f64,
+        duration: D,
+        vorigin: f64,
+        torigin: impl Into<Timestamp>,
+    ) -> Result<Vec<TBox>, Error> {
+        let duration = duration.to_interval()?;
+        let torigin = torigin.into().micros();
+        let mut count = 0;
+        unsafe {
+            let tiles = if self.is_int() {
+                ffi::tintbox_tile_list(
+                    self.ptr(),
+                    vsize as i32,
+                    &duration,
+                    vorigin as i32,
+                    torigin,
+                    &mut count,
+                )
+            } else {
+                ffi::tfloatbox_tile_list(self.ptr(), vsize, &duration, vorigin, torigin, &mut count)
+            };
+            if tiles.is_null() {
+                // todo;; check the meos error
+                return Err(MeosError(-999));
+            }
+            let res = (0..count as usize)
+                .filter_map(|i| TBox::from_ptr(ffi::tbox_copy(tiles.add(i))).ok())
+                .collect();
+            free(tiles.cast());
+            Ok(res)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::prelude::*;
+    use crate::span::FloatSpan;
     use crate::tbox::TBox;
+    use crate::tz::Timestamp;
     use crate::{finalize, init};
 
     #[cfg(test)]
@@ -131,22 +432,22 @@ mod tests {
     fn test_tbox_wkt() {
         let a = "TBOX X([1.1, 4.0))";
         let o = "TBOXFLOAT X([1.1, 4))";
-        let b = TBox::from_wkt(a).unwrap().as_wkt();
+        let b = TBox::from_wkt(a).unwrap().as_wkt(6).unwrap();
         assert_eq!(o, &b);
 
         let a = "TBOX XT((4, 12),[2001-01-01, 2001-10-01])";
         let o = "TBOXFLOAT XT((4, 12),[2001-01-01 00:00:00-05, 2001-10-01 00:00:00-04])";
-        let b = TBox::from_wkt(a).unwrap().as_wkt();
+        let b = TBox::from_wkt(a).unwrap().as_wkt(6).unwrap();
         assert_eq!(o, &b);
 
         let a = "TBOX XT([1.0, 4.0),[2001-01-01, 2001-01-02])";
         let o = "TBOXFLOAT XT([1, 4),[2001-01-01 00:00:00-05, 2001-01-02 00:00:00-05])";
-        let b = TBox::from_wkt(a).unwrap().as_wkt();
+        let b = TBox::from_wkt(a).unwrap().as_wkt(6).unwrap();
         assert_eq!(o, &b);
 
         let a = "TBOX T([2001-01-01, 2001-01-02])";
         let o = "TBOX T([2001-01-01 00:00:00-05, 2001-01-02 00:00:00-05])";
-        let b = TBox::from_wkt(a).unwrap().as_wkt();
+        let b = TBox::from_wkt(a).unwrap().as_wkt(6).unwrap();
         assert_eq!(o, &b);
     }
 
@@ -196,6 +497,139 @@ mod tests {
 
     #[test]
     fn test_tbox_int() {
-        let b = TBox::from_int(1);
+        let b = TBox::from_int(1).unwrap();
+        assert_eq!("TBOXINT X([1, 2))", b.as_wkt(6).unwrap());
+    }
+
+    #[test]
+    fn test_tbox_float() {
+        let b = TBox::from_float(1.5).unwrap();
+        assert_eq!("TBOXFLOAT X([1.5, 1.5])", b.as_wkt(6).unwrap());
+    }
+
+    #[test]
+    fn test_tbox_value_span() {
+        let s = FloatSpan::new(1.0, 4.0, true, false).unwrap();
+        let b = TBox::from_value_span(&s).unwrap();
+        assert_eq!("TBOXFLOAT X([1, 4))", b.as_wkt(6).unwrap());
+    }
+
+    #[test]
+    fn test_tbox_accessors() {
+        let b = TBox::from_wkt("TBOX X([1.0, 4.0))").unwrap();
+        assert_eq!(Some(1.0), b.xmin());
+        assert_eq!(Some(4.0), b.xmax());
+        assert_eq!(Some(true), b.xmin_inc());
+        assert_eq!(Some(false), b.xmax_inc());
+        assert!(b.has_x());
+        assert!(!b.has_t());
+        assert_eq!(None, b.tmin());
+
+        let b = TBox::from_wkt("TBOX T([2000-01-01 00:00:00+00, 2000-01-02 00:00:00+00])").unwrap();
+        assert!(!b.has_x());
+        assert_eq!(Some(Timestamp::from_micros(0)), b.tmin());
+        assert_eq!(Some(Timestamp::from_micros(86_400_000_000)), b.tmax());
+    }
+
+    #[test]
+    fn test_tbox_set_ops() {
+        let a = TBox::from_wkt("TBOX X([1.0, 4.0))").unwrap();
+        let b = TBox::from_wkt("TBOX X([2.0, 6.0))").unwrap();
+        assert_eq!(
+            "TBOXFLOAT X([1, 6))",
+            a.union(&b).unwrap().as_wkt(6).unwrap()
+        );
+        assert_eq!(
+            "TBOXFLOAT X([2, 4))",
+            a.intersection(&b).unwrap().as_wkt(6).unwrap()
+        );
+
+        let c = TBox::from_wkt("TBOX X([10.0, 11.0))").unwrap();
+        assert!(a.intersection(&c).is_none());
+
+        assert_eq!(
+            "TBOXFLOAT X([0, 5))",
+            a.expand_value(1.0).unwrap().as_wkt(6).unwrap()
+        );
+        assert_eq!(
+            "TBOXFLOAT X([2, 5))",
+            a.shift_scale_value(Some(1.0), None)
+                .unwrap()
+                .as_wkt(6)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tbox_position() {
+        let a = TBox::from_wkt("TBOX X([1.0, 2.0])").unwrap();
+        let b = TBox::from_wkt("TBOX X([3.0, 4.0])").unwrap();
+        assert!(a.left(&b));
+        assert!(a.overleft(&b));
+        assert!(!a.right(&b));
+        assert!(b.right(&a));
+        assert!(b.overright(&a));
+    }
+
+    #[test]
+    fn test_tbox_shift_scale_value() {
+        let b = TBox::from_wkt("TBOXINT X([1, 5))").unwrap();
+        let shifted = b.shift_scale_value(Some(2.0), None).unwrap();
+        assert_eq!("TBOXINT X([3, 7))", shifted.as_wkt(6).unwrap());
+
+        let b = TBox::from_wkt("TBOXFLOAT X([1, 5))").unwrap();
+        let scaled = b.shift_scale_value(Some(1.5), Some(2.0)).unwrap();
+        assert_eq!("TBOXFLOAT X([2.5, 4.5))", scaled.as_wkt(6).unwrap());
+    }
+
+    #[test]
+    fn test_tbox_tiles() {
+        let day = std::time::Duration::from_secs(86_400);
+        let b = TBox::from_wkt("TBOX XT([1, 9],[2000-01-01 01:00:00+00, 2000-01-01 23:00:00+00])")
+            .unwrap();
+        let tiles = b.tiles(5.0, day, 0.0, Timestamp::from_micros(0)).unwrap();
+        assert_eq!(2, tiles.len());
+        assert_eq!(Some(0.0), tiles[0].xmin());
+        assert_eq!(Some(Timestamp::from_micros(0)), tiles[0].tmin());
+
+        assert_eq!(
+            3,
+            b.tiles(5.0, day, 2.5, Timestamp::from_micros(0))
+                .unwrap()
+                .len()
+        );
+        let noon = Timestamp::from_micros(43_200_000_000);
+        assert_eq!(4, b.tiles(5.0, day, 0.0, noon).unwrap().len());
+
+        let b =
+            TBox::from_wkt("TBOXINT XT([1, 9],[2000-01-01 01:00:00+00, 2000-01-01 23:00:00+00])")
+                .unwrap();
+        assert_eq!(
+            2,
+            b.tiles(5.0, day, 0.0, Timestamp::from_micros(0))
+                .unwrap()
+                .len()
+        );
+        assert!(b.tiles(5.0, std::time::Duration::MAX, 0.0, noon).is_err());
+    }
+
+    #[test]
+    fn test_tbox_contains_temporal() {
+        let t = TSeq::from_wkt_as("[1@2000-01-01, 3@2000-01-02]", TemporalType::TFloat).unwrap();
+
+        let a = TBox::from_wkt("TBOX XT([0, 4],[2000-01-01, 2000-01-03])").unwrap();
+        assert!(a.contains_temporal(&t).unwrap());
+        assert!(!a.contained_in(&t).unwrap());
+
+        let b = TBox::from_wkt("TBOX XT([1.5, 2.5],[2000-01-01, 2000-01-02])").unwrap();
+        assert!(!b.contains_temporal(&t).unwrap());
+        assert!(b.contained_in(&t).unwrap());
+
+        let trip = TSeq::from_wkt("[POINT(1 1)@2000-01-01 00:00:00+00]").unwrap();
+        assert!(matches!(
+            a.contains_temporal(&trip),
+            Err(Error::WrongTemporalType)
+        ));
+        assert!(a.contained_in(&trip).is_err());
     }
 }
@@ -1,21 +1,206 @@
-use crate::{c_str_to_slice, try_cstr_to_str, TPtrCtr, Type};
-use libc::{c_char, free};
+use crate::span::TsTzSpan;
+use crate::stbox::STBox;
+use crate::tbox::TBox;
+use crate::tinst::TInst;
+use crate::tseq::TSeq;
+use crate::tset::TSet;
+use crate::tz::{Interval, Timestamp, ToInterval};
+use crate::{
+    take_bytes, take_hex, to_c_str, try_cstr_to_str, Interp, TPtr, TPtrCtr, TPtrFrom, TemporalType,
+    Type, Wkb, DEFAULT_PRECISION,
+};
+use libc::free;
 use meos_sys as ffi;
 use meos_sys::WKB_EXTENDED;
 
 use crate::error::Error;
-use std::ptr::null_mut;
+use std::fmt::{Display, Formatter};
+use std::ptr::{null, null_mut, NonNull};
+
+/// The bounding box of a temporal, which depends on its base type
+#[derive(Debug)]
+pub enum BoundingBox {
+    /// temporal points
+    STBox(STBox),
+    /// temporal numbers
+    TBox(TBox),
+    /// temporals without a value dimension, such as tbool and ttext
+    Period(TsTzSpan),
+}
+
+/// Indentation of the MF-JSON output
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum MfJsonIndent {
+    /// everything on one line
+    #[default]
+    Compact,
+    /// one member per line, indented by two spaces
+    Spaces,
+    /// one member per line, indented by tabs
+    Tabs,
+}
+
+impl MfJsonIndent {
+    /// the json-c output flags
+    fn flags(&self) -> i32 {
+        match self {
+            MfJsonIndent::Compact => 0,
+            MfJsonIndent::Spaces => 2,
+            MfJsonIndent::Tabs => 2 | 8,
+        }
+    }
+}
+
+/// Options of the MF-JSON output, see [Temporal::to_mf_json_with]
+///
+/// The default is what [Temporal::to_mf_json] writes, compact with a bounding
+/// box, 6 decimal digits and no CRS.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MfJsonOptions {
+    /// include the `bbox` member
+    pub with_bbox: bool,
+    pub indent: MfJsonIndent,
+    /// the maximum number of decimal digits of coordinates and values
+    pub precision: i32,
+    /// the name of the CRS of temporal points, such as `EPSG:4326`
+    pub crs: Option<String>,
+}
+
+impl Default for MfJsonOptions {
+    fn default() -> Self {
+        Self {
+            with_bbox: true,
+            indent: MfJsonIndent::Compact,
+            precision: 6,
+            crs: None,
+        }
+    }
+}
+
+impl MfJsonOptions {
+    /// the SRID of an `EPSG:n` or `urn:ogc:def:crs:EPSG::n` CRS name
+    fn srid(&self) -> Option<i32> {
+        let crs = self.crs.as_deref()?;
+        let code = crs
+            .strip_prefix("EPSG:")
+            .or_else(|| crs.strip_prefix("urn:ogc:def:crs:EPSG::"))?;
+        if code.is_empty() || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        code.parse().ok()
+    }
+}
 
 #[allow(private_bounds)]
-pub trait Temporal: TPtrCtr {
+pub trait Temporal: TPtrCtr + TPtrFrom {
     fn from_wkt(wkt: &str) -> Result<Self, Error>
     where
         Self: Sized;
 
+    /// parses a temporal of the given base type
+    fn from_wkt_as(wkt: &str, ty: TemporalType) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Self::from_ptr(temporal_in(wkt, ty)?)
+    }
+
+    /// parses a temporal from MF-JSON
+    fn from_mf_json(json: &str) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let cstr = to_c_str(json)?;
+        unsafe { Self::from_ptr(ffi::temporal_from_mfjson(cstr.as_ptr())) }
+    }
+
+    /// parses MF-JSON written with `options`
+    ///
+    /// Temporal points without a `crs` member get the SRID of `options.crs`,
+    /// so output written without the CRS reads back in its reference system.
+    fn from_mf_json_with(json: &str, options: &MfJsonOptions) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let t = Self::from_mf_json(json)?;
+        match options.srid() {
+            Some(srid)
+                if t.temporal_type()?.is_point() && unsafe { ffi::tpoint_srid(t.ptr()) } == 0 =>
+            unsafe { Self::from_ptr(ffi::tpoint_set_srid(t.ptr(), srid)) },
+            _ => Ok(t),
+        }
+    }
+
     fn ttype(&self) -> Type;
 
+    /// the number of distinct instants
+    fn num_instants(&self) -> usize {
+        unsafe { ffi::temporal_num_instants(self.ptr()) as usize }
+    }
+
+    /// copies of the distinct instants, in time order
+    fn instants(&self) -> Vec<TInst> {
+        (1..=self.num_instants() as i32)
+            .filter_map(|n| unsafe {
+                TInst::from_ptr(ffi::temporal_instant_n(self.ptr(), n).cast()).ok()
+            })
+            .collect()
+    }
+
+    /// the base type of the temporal, an error for meos types without a [TemporalType]
+    fn temporal_type(&self) -> Result<TemporalType, Error> {
+        let t = unsafe { (*self.ptr()).temptype } as ffi::meosType;
+        TemporalType::from_meos(t).ok_or(Error::WrongTemporalType)
+    }
+
+    /// the interpolation of a sequence or sequence set, `None` for instants
+    fn interp(&self) -> Option<Interp> {
+        let s = try_cstr_to_str(unsafe { ffi::temporal_interp(self.ptr()) }).ok()?;
+        match s.as_str() {
+            "Discrete" => Some(Interp::Discrete),
+            "Step" => Some(Interp::Step),
+            "Linear" => Some(Interp::Linear),
+            _ => None,
+        }
+    }
+
     fn to_mf_json(&self) -> Result<String, Error> {
-        unsafe { try_cstr_to_str(ffi::temporal_as_mfjson(self.ptr(), true, 0, 6, null_mut())) }
+        self.to_mf_json_with(&MfJsonOptions::default())
+    }
+
+    /// the MF-JSON of the temporal, see [MfJsonOptions]
+    fn to_mf_json_with(&self, options: &MfJsonOptions) -> Result<String, Error> {
+        let crs = options.crs.as_deref().map(to_c_str).transpose()?;
+        let srs = crs.as_ref().map_or(null_mut(), |c| c.as_ptr().cast_mut());
+        unsafe {
+            try_cstr_to_str(ffi::temporal_as_mfjson(
+                self.ptr(),
+                options.with_bbox,
+                options.indent.flags(),
+                options.precision,
+                srs,
+            ))
+        }
+    }
+
+    /// the WKT of the temporal with at most `precision` decimal digits
+    fn as_wkt(&self, precision: i32) -> Result<String, Error> {
+        unsafe {
+            let cstr = if self.temporal_type().is_ok_and(|t| t.is_point()) {
+                ffi::tpoint_as_text(self.ptr(), precision)
+            } else {
+                ffi::temporal_out(self.ptr(), precision)
+            };
+            try_cstr_to_str(cstr)
+        }
+    }
+
+    /// the WKT prefixed by the SRID of temporal points, as read by [Temporal::from_wkt]
+    fn as_ewkt(&self, precision: i32) -> Result<String, Error> {
+        if !self.temporal_type().is_ok_and(|t| t.is_point()) {
+            return self.as_wkt(precision);
+        }
+        unsafe { try_cstr_to_str(ffi::tpoint_as_ewkt(self.ptr(), precision)) }
     }
 
     fn as_bytes(&self) -> &[u8] {
@@ -27,40 +212,587 @@ pub trait Temporal: TPtrCtr {
         }
     }
 
-    fn as_hex(&self) -> Option<String> {
-        let mut szout: usize = 0;
+    /// the MF-JSON of the temporal without a bounding box
+    fn as_json(&self) -> Option<String> {
+        let options = MfJsonOptions {
+            with_bbox: false,
+            ..Default::default()
+        };
+        self.to_mf_json_with(&options).ok()
+    }
+
+    /// returns the starting timestamp
+    fn ts(&self) -> Timestamp {
+        self.start_timestamp()
+    }
+
+    fn start_timestamp(&self) -> Timestamp {
+        Timestamp::from_micros(unsafe { ffi::temporal_start_timestamptz(self.ptr()) })
+    }
+
+    fn end_timestamp(&self) -> Timestamp {
+        Timestamp::from_micros(unsafe { ffi::temporal_end_timestamptz(self.ptr()) })
+    }
+
+    /// the time over which the temporal is defined, or its whole extent when `ignore_gaps`
+    fn duration(&self, ignore_gaps: bool) -> Interval {
         unsafe {
-            let bytes = ffi::temporal_as_hexwkb(self.ptr().cast(), WKB_EXTENDED as u8, &mut szout);
-            let r = c_str_to_slice(&(bytes as *const c_char)).map(|s| s.to_owned());
-            free(bytes.cast());
-            r
+            let i = ffi::temporal_duration(self.ptr(), ignore_gaps);
+            let d = Interval::from(&*i);
+            free(i.cast());
+            d
         }
     }
 
-    fn as_json(&self) -> Option<String> {
+    /// the instant of the temporal at a timestamp, `None` when it is not defined there
+    fn at_timestamp(&self, t: Timestamp) -> Option<TInst> {
+        unsafe { TInst::from_ptr(ffi::temporal_at_timestamp(self.ptr(), t.micros())).ok() }
+    }
+
+    /// the value of a temporal number at a timestamp
+    ///
+    /// `None` when the temporal is not defined at `t` or is not a tint or tfloat.
+    fn value_at(&self, t: Timestamp) -> Option<f64> {
         unsafe {
-            let bytes = ffi::temporal_as_mfjson(self.ptr().cast(), false, 0, 6, null_mut());
-            let r = c_str_to_slice(&(bytes as *const c_char)).map(|s| s.to_owned());
-            free(bytes.cast());
-            r
+            match self.temporal_type().ok()? {
+                TemporalType::TFloat => {
+                    let mut v = 0.0;
+                    ffi::tfloat_value_at_timestamp(self.ptr(), t.micros(), true, &mut v)
+                        .then_some(v)
+                }
+                TemporalType::TInt => {
+                    let mut v = 0;
+                    ffi::tint_value_at_timestamp(self.ptr(), t.micros(), true, &mut v)
+                        .then_some(v as f64)
+                }
+                _ => None,
+            }
         }
     }
 
-    /// returns the starting timestamp
-    fn ts(&self) -> i64 {
-        unsafe { ffi::temporal_start_timestamptz(self.ptr()) }
+    /// the temporal restricted to a span of time, `None` when they do not intersect
+    fn at_time_span(&self, span: &TsTzSpan) -> Option<Box<dyn Temporal>> {
+        unsafe { temp_from(ffi::temporal_at_period(self.ptr(), span.ptr())) }
+    }
+
+    /// the temporal restricted to the complement of a span of time
+    fn minus_time_span(&self, span: &TsTzSpan) -> Option<Box<dyn Temporal>> {
+        unsafe { temp_from(ffi::temporal_minus_period(self.ptr(), span.ptr())) }
+    }
+
+    /// the time span over which the temporal is defined
+    fn time_span(&self) -> Result<TsTzSpan, Error> {
+        unsafe { TsTzSpan::from_ptr(ffi::temporal_to_period(self.ptr())) }
+    }
+
+    /// the bounding box of the temporal, see [BoundingBox]
+    fn bounding_box(&self) -> Result<BoundingBox, Error> {
+        unsafe {
+            match self.temporal_type()? {
+                t if t.is_point() => {
+                    STBox::from_ptr(ffi::tpoint_to_stbox(self.ptr())).map(BoundingBox::STBox)
+                }
+                t if t.is_number() => {
+                    TBox::from_ptr(ffi::tnumber_to_tbox(self.ptr())).map(BoundingBox::TBox)
+                }
+                _ => self.time_span().map(BoundingBox::Period),
+            }
+        }
+    }
+
+    /// samples the temporal every `duration` starting from `origin`
+    ///
+    /// The subtype of the result depends on `interp`, a discrete sample of a
+    /// sequence set is a sequence.
+    fn tsample<D: ToInterval>(
+        &self,
+        duration: D,
+        origin: Timestamp,
+        interp: Interp,
+    ) -> Result<Box<dyn Temporal>, Error>
+    where
+        Self: Sized,
+    {
+        let duration = duration.to_interval()?;
+        unsafe {
+            <Box<dyn Temporal>>::from_ptr(ffi::temporal_tsample(
+                self.ptr(),
+                &duration,
+                origin.micros(),
+                interp.into(),
+            ))
+        }
+    }
+
+    /// reduces the temporal precision to buckets of `duration` starting from `origin`
+    ///
+    /// The subtype of the result may differ, an instant per bucket remains of a
+    /// discrete sequence.
+    fn tprecision<D: ToInterval>(
+        &self,
+        duration: D,
+        origin: Timestamp,
+    ) -> Result<Box<dyn Temporal>, Error>
+    where
+        Self: Sized,
+    {
+        let duration = duration.to_interval()?;
+        unsafe {
+            <Box<dyn Temporal>>::from_ptr(ffi::temporal_tprecision(
+                self.ptr(),
+                &duration,
+                origin.micros(),
+            ))
+        }
+    }
+
+    /// shifts the temporal by `shift` and/or scales it to span `duration`
+    fn shift_scale_time<D: ToInterval>(
+        &self,
+        shift: Option<D>,
+        duration: Option<D>,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let shift = shift.map(|d| d.to_interval()).transpose()?;
+        let duration = duration.map(|d| d.to_interval()).transpose()?;
+        unsafe {
+            Self::from_ptr(ffi::temporal_shift_scale_time(
+                self.ptr(),
+                shift.as_ref().map_or(null(), |i| i as *const _),
+                duration.as_ref().map_or(null(), |i| i as *const _),
+            ))
+        }
+    }
+
+    /// Douglas-Peucker simplification keeping the values within `eps_dist`
+    ///
+    /// With `synchronized` the distance to a segment is measured at the same
+    /// time, which keeps changes of speed.
+    fn simplify_dp(&self, eps_dist: f64, synchronized: bool) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        unsafe {
+            Self::from_ptr(ffi::temporal_simplify_dp(
+                self.ptr(),
+                eps_dist,
+                synchronized,
+            ))
+        }
+    }
+
+    /// discrete Fréchet distance to another temporal
+    fn frechet_distance(&self, other: &dyn Temporal) -> Result<f64, Error> {
+        ensure_same_type(self, other)?;
+        similarity(unsafe { ffi::temporal_frechet_distance(self.ptr(), other.ptr()) })
+    }
+
+    /// dynamic time warp distance to another temporal
+    fn dyntimewarp_distance(&self, other: &dyn Temporal) -> Result<f64, Error> {
+        ensure_same_type(self, other)?;
+        similarity(unsafe { ffi::temporal_dyntimewarp_distance(self.ptr(), other.ptr()) })
+    }
+
+    /// Hausdorff distance to another temporal
+    fn hausdorff_distance(&self, other: &dyn Temporal) -> Result<f64, Error> {
+        ensure_same_type(self, other)?;
+        similarity(unsafe { ffi::temporal_hausdorff_distance(self.ptr(), other.ptr()) })
+    }
+
+    /// instant index pairs of the Fréchet matching path to another temporal
+    fn frechet_path(&self, other: &dyn Temporal) -> Result<Vec<(usize, usize)>, Error> {
+        ensure_same_type(self, other)?;
+        let mut count = 0;
+        unsafe {
+            match_path(
+                ffi::temporal_frechet_path(self.ptr(), other.ptr(), &mut count),
+                count,
+            )
+        }
+    }
+
+    /// instant index pairs of the dynamic time warp matching path to another temporal
+    fn dyntimewarp_path(&self, other: &dyn Temporal) -> Result<Vec<(usize, usize)>, Error> {
+        ensure_same_type(self, other)?;
+        let mut count = 0;
+        unsafe {
+            match_path(
+                ffi::temporal_dyntimewarp_path(self.ptr(), other.ptr(), &mut count),
+                count,
+            )
+        }
     }
 }
 
-// ----------------------------------
+/// the base type of a temporal can only be checked at run time
+pub(crate) fn ensure_number<T: Temporal + ?Sized>(t: &T) -> Result<(), Error> {
+    if t.temporal_type()?.is_number() {
+        Ok(())
+    } else {
+        Err(Error::WrongTemporalType)
+    }
+}
 
-// temporal pointer to a boxed iface
-// fn temp_from(p: NonNull<ffi::Temporal>) -> Box<dyn Temporal> {
-//     let t: ffi::tempSubtype = unsafe { (*p.as_ptr()).subtype.into() };
-//     match t {
-//         ffi::tempSubtype_TINSTANT => Box::new(TInst { ptr: p.cast() }),
-//         ffi::tempSubtype_TSEQUENCE => Box::new(TSeq { ptr: p.cast() }),
-//         ffi::tempSubtype_TSEQUENCESET => Box::new(TSet { ptr: p.cast() }),
-//         _ => unreachable!("invalid tempSubtype: probably ANYTEMPSUBTYPE"),
-//     }
-// }
+/// similarity is only defined between temporals of the same base type
+fn ensure_same_type<T: Temporal + ?Sized>(t: &T, other: &dyn Temporal) -> Result<(), Error> {
+    if t.temporal_type()? == other.temporal_type()? {
+        Ok(())
+    } else {
+        Err(Error::WrongTemporalType)
+    }
+}
+
+/// meos reports a failed similarity as -1 or `DBL_MAX` instead of a distance
+fn similarity(distance: f64) -> Result<f64, Error> {
+    if distance < 0.0 || distance == f64::MAX {
+        // todo;; check the meos error
+        Err(Error::MeosError(-999))
+    } else {
+        Ok(distance)
+    }
+}
+
+/// takes ownership of a meos matching path
+unsafe fn match_path(path: *mut ffi::Match, count: i32) -> Result<Vec<(usize, usize)>, Error> {
+    if path.is_null() {
+        // todo;; check the meos error
+        return Err(Error::MeosError(-999));
+    }
+    let res = usize::try_from(count).map(|count| {
+        std::slice::from_raw_parts(path, count)
+            .iter()
+            .map(|m| (m.i as usize, m.j as usize))
+            .collect()
+    });
+    free(path.cast());
+    res.map_err(|_| Error::MeosError(-999))
+}
+
+impl<T: Temporal> Wkb for T {
+    fn to_wkb(&self) -> Vec<u8> {
+        let mut size = 0;
+        unsafe {
+            let bytes = ffi::temporal_as_wkb(self.ptr(), WKB_EXTENDED as u8, &mut size);
+            take_bytes(bytes, size)
+        }
+    }
+
+    fn from_wkb(wkb: &[u8]) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::temporal_from_wkb(wkb.as_ptr(), wkb.len())) }
+    }
+
+    fn as_hex(&self) -> Option<String> {
+        let mut size = 0;
+        unsafe {
+            take_hex(ffi::temporal_as_hexwkb(
+                self.ptr(),
+                WKB_EXTENDED as u8,
+                &mut size,
+            ))
+        }
+    }
+
+    fn from_hex(hex: &str) -> Result<Self, Error> {
+        let cstr = to_c_str(hex)?;
+        unsafe { Self::from_ptr(ffi::temporal_from_hexwkb(cstr.as_ptr())) }
+    }
+}
+
+/// parses a temporal of the given base type whatever its subtype
+pub fn temporal_from_wkt(wkt: &str, ty: TemporalType) -> Result<Box<dyn Temporal>, Error> {
+    // todo;; check the meos error
+    temp_from(temporal_in(wkt, ty)?).ok_or(Error::MeosError(-999))
+}
+
+fn temporal_in(wkt: &str, ty: TemporalType) -> Result<TPtr, Error> {
+    let cstr = to_c_str(wkt)?;
+    unsafe {
+        Ok(match ty {
+            TemporalType::TBool => ffi::tbool_in(cstr.as_ptr()),
+            TemporalType::TInt => ffi::tint_in(cstr.as_ptr()),
+            TemporalType::TFloat => ffi::tfloat_in(cstr.as_ptr()),
+            TemporalType::TText => ffi::ttext_in(cstr.as_ptr()),
+            TemporalType::TGeomPoint => ffi::tgeompoint_in(cstr.as_ptr()),
+            TemporalType::TGeogPoint => ffi::tgeogpoint_in(cstr.as_ptr()),
+        })
+    }
+}
+
+// temporals of an unknown subtype, such as the results of at_time_span or
+// temporal_from_wkt, are temporals themselves
+impl TPtrCtr for Box<dyn Temporal> {
+    fn ptr(&self) -> TPtr {
+        self.as_ref().ptr()
+    }
+}
+
+impl TPtrFrom for Box<dyn Temporal> {
+    fn from_ptr(ptr: TPtr) -> Result<Self, Error> {
+        // todo;; check the meos error
+        temp_from(ptr).ok_or(Error::MeosError(-999))
+    }
+}
+
+impl Temporal for Box<dyn Temporal> {
+    fn from_wkt(wkt: &str) -> Result<Self, Error> {
+        temporal_from_wkt(wkt, TemporalType::TGeomPoint)
+    }
+
+    fn ttype(&self) -> Type {
+        self.as_ref().ttype()
+    }
+}
+
+impl Display for dyn Temporal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .as_ewkt(DEFAULT_PRECISION)
+                .map_err(|_| std::fmt::Error)?,
+        )
+    }
+}
+
+/// temporal pointer to a boxed iface
+pub(crate) fn temp_from(ptr: TPtr) -> Option<Box<dyn Temporal>> {
+    let p = NonNull::new(ptr)?;
+    let t = unsafe { (*p.as_ptr()).subtype } as ffi::tempSubtype;
+    let b: Box<dyn Temporal> = match t {
+        ffi::tempSubtype_TINSTANT => Box::new(TInst::from_ptr(ptr).ok()?),
+        ffi::tempSubtype_TSEQUENCE => Box::new(TSeq::from_ptr(ptr).ok()?),
+        ffi::tempSubtype_TSEQUENCESET => Box::new(TSet::from_ptr(ptr).ok()?),
+        _ => unreachable!("invalid tempSubtype: probably ANYTEMPSUBTYPE"),
+    };
+    Some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::prelude::*;
+    use crate::tz::Timestamp;
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    const WKT: &str = "SRID=4326;[POINT(1.123456789 1)@2000-01-01 00:00:00+00, \
+                       POINT(2 2)@2000-01-02 00:00:00+00]";
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn speeds() -> TSeq {
+        TSeq::from_wkt_as(
+            "[1@2000-01-01 00:00:00+00, 3@2000-01-01 00:02:00+00]",
+            TemporalType::TFloat,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tsample() {
+        let origin = Timestamp::from_micros(0);
+        let linear = speeds().tsample(MINUTE, origin, Interp::Linear).unwrap();
+        assert_eq!(Type::Sequence, linear.ttype());
+        assert_eq!(3, linear.num_instants());
+        let minute = Timestamp::from_micros(60_000_000);
+        assert_eq!(Some(2.0), linear.value_at(minute));
+
+        let set = TSet::from_wkt_as(
+            "{[1@2000-01-01 00:00:00+00, 3@2000-01-01 00:02:00+00], \
+             [5@2000-01-01 00:05:00+00, 5@2000-01-01 00:06:00+00]}",
+            TemporalType::TFloat,
+        )
+        .unwrap();
+        let discrete = set.tsample(MINUTE, origin, Interp::Discrete).unwrap();
+        assert_eq!(Type::Sequence, discrete.ttype());
+        assert_eq!(5, discrete.num_instants());
+
+        assert!(speeds()
+            .tsample(Duration::MAX, origin, Interp::Linear)
+            .is_err());
+    }
+
+    #[test]
+    fn test_tprecision() {
+        let origin = Timestamp::from_micros(0);
+        let hourly = speeds()
+            .tprecision(Duration::from_secs(3600), origin)
+            .unwrap();
+        assert_eq!(1, hourly.num_instants());
+        assert_eq!(Some(2.0), hourly.value_at(origin));
+    }
+
+    #[test]
+    fn test_shift_scale_time() {
+        let shifted = speeds().shift_scale_time(Some(MINUTE), None).unwrap();
+        assert_eq!(
+            Timestamp::from_micros(60_000_000),
+            shifted.start_timestamp()
+        );
+
+        let scaled = speeds()
+            .shift_scale_time(None, Some(Duration::from_secs(3600)))
+            .unwrap();
+        assert_eq!(
+            Timestamp::from_micros(3_600_000_000),
+            scaled.end_timestamp()
+        );
+        assert!(speeds()
+            .shift_scale_time(Some(Duration::MAX), None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_time_span() {
+        let span = speeds().time_span().unwrap();
+        assert_eq!(Timestamp::from_micros(0), span.lower());
+        assert_eq!(Timestamp::from_micros(120_000_000), span.upper());
+        assert!(span.lower_inc());
+        assert!(span.upper_inc());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        match speeds().bounding_box().unwrap() {
+            BoundingBox::TBox(b) => {
+                assert_eq!(Some(1.0), b.xmin());
+                assert_eq!(Some(3.0), b.xmax());
+            }
+            b => panic!("expected a tbox, got {b:?}"),
+        }
+
+        match TSeq::from_wkt(WKT).unwrap().bounding_box().unwrap() {
+            BoundingBox::STBox(b) => {
+                assert_eq!(4326, b.srid());
+                assert_eq!(Some(2.0), b.ymax());
+            }
+            b => panic!("expected an stbox, got {b:?}"),
+        }
+
+        let flags = TSeq::from_wkt_as(
+            "[true@2000-01-01 00:00:00+00, false@2000-01-01 00:01:00+00]",
+            TemporalType::TBool,
+        )
+        .unwrap();
+        match flags.bounding_box().unwrap() {
+            BoundingBox::Period(p) => assert_eq!(Timestamp::from_micros(60_000_000), p.upper()),
+            b => panic!("expected a period, got {b:?}"),
+        }
+    }
+
+    #[test]
+    fn test_similarity() {
+        let a = TSeq::from_wkt(
+            "[POINT(0 0)@2000-01-01 00:00:00+00, POINT(1 0)@2000-01-01 00:01:00+00]",
+        )
+        .unwrap();
+        let b = TSeq::from_wkt(
+            "[POINT(0 1)@2000-01-01 00:00:00+00, POINT(1 1)@2000-01-01 00:01:00+00]",
+        )
+        .unwrap();
+        assert_eq!(1.0, a.frechet_distance(&b).unwrap());
+        assert_eq!(2.0, a.dyntimewarp_distance(&b).unwrap());
+        assert_eq!(1.0, a.hausdorff_distance(&b).unwrap());
+        assert_eq!(vec![(0, 0), (1, 1)], a.frechet_path(&b).unwrap());
+        assert_eq!(vec![(0, 0), (1, 1)], a.dyntimewarp_path(&b).unwrap());
+
+        assert!(matches!(
+            a.frechet_distance(&speeds()),
+            Err(Error::WrongTemporalType)
+        ));
+        assert!(a.dyntimewarp_path(&speeds()).is_err());
+    }
+
+    #[test]
+    fn test_wkt_precision() {
+        let seq = TSeq::from_wkt(WKT).unwrap();
+        assert_eq!(
+            "[POINT(1.12 1)@2000-01-01 00:00:00+00, POINT(2 2)@2000-01-02 00:00:00+00]",
+            seq.as_wkt(2).unwrap()
+        );
+        assert!(seq
+            .as_ewkt(2)
+            .unwrap()
+            .starts_with("SRID=4326;[POINT(1.12 1)@"));
+        assert!(seq.to_string().contains("1.123456789"));
+
+        let speed = TSeq::from_wkt_as("[1.25@2000-01-01]", TemporalType::TFloat).unwrap();
+        assert_eq!(speed.as_wkt(1).unwrap(), speed.as_ewkt(1).unwrap());
+        let boxed: Box<dyn Temporal> = Box::new(speed);
+        assert!(boxed.to_string().starts_with("[1.25@"));
+    }
+
+    #[test]
+    fn test_mf_json_options() {
+        let seq = TSeq::from_wkt(WKT).unwrap();
+        let options = MfJsonOptions {
+            with_bbox: false,
+            indent: MfJsonIndent::Spaces,
+            precision: 2,
+            crs: Some("EPSG:4326".to_string()),
+        };
+        let json = seq.to_mf_json_with(&options).unwrap();
+        assert!(json.contains('\n'));
+        assert!(json.contains("EPSG:4326"));
+        assert!(json.contains("1.12"));
+        assert!(!json.contains("1.123"));
+        assert!(!json.contains("bbox"));
+        assert!(seq.to_mf_json().unwrap().contains("bbox"));
+
+        let back = TSeq::from_mf_json_with(&json, &options).unwrap();
+        assert_eq!(4326, back.srid().unwrap());
+        assert_eq!(2, back.num_instants());
+    }
+
+    #[test]
+    fn test_mf_json_default_crs() {
+        let seq = TSeq::from_wkt(WKT).unwrap();
+        let options = MfJsonOptions {
+            crs: Some("EPSG:3857".to_string()),
+            ..Default::default()
+        };
+        let json = seq.set_srid(0).unwrap().to_mf_json().unwrap();
+        let back = TSeq::from_mf_json_with(&json, &options).unwrap();
+        assert_eq!(3857, back.srid().unwrap());
+    }
+
+    #[test]
+    fn test_mf_json_srid() {
+        let srid = |crs: &str| {
+            MfJsonOptions {
+                crs: Some(crs.to_string()),
+                ..Default::default()
+            }
+            .srid()
+        };
+        assert_eq!(Some(4326), srid("EPSG:4326"));
+        assert_eq!(Some(3857), srid("urn:ogc:def:crs:EPSG::3857"));
+        assert_eq!(None, srid("OGC:CRS84"));
+        assert_eq!(None, srid("urn:ogc:def:crs:OGC:1.3:84"));
+        assert_eq!(None, srid("EPSG:+4326"));
+
+        // the crs only applies to temporal points
+        let speed =
+            TSeq::from_wkt_as("[1.5@2000-01-01 00:00:00+00]", TemporalType::TFloat).unwrap();
+        let json = speed.to_mf_json().unwrap();
+        let options = MfJsonOptions {
+            crs: Some("EPSG:4326".to_string()),
+            ..Default::default()
+        };
+        let back = TSeq::from_mf_json_with(&json, &options).unwrap();
+        assert!(speed == back);
+    }
+}
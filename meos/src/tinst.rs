@@ -1,10 +1,13 @@
 use crate::error::Error;
 use crate::error::Error::{MeosError, WrongTemporalType};
+use crate::stbox::STBox;
 use crate::temp::Temporal;
-use crate::{to_c_str, TPtrCtr, Type};
+use crate::tz::Timestamp;
+use crate::{geom_from_wkt, to_c_str, TPtr, TPtrCtr, TPtrFrom, Type, DEFAULT_PRECISION};
 use libc::free;
 use meos_sys as ffi;
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use std::ptr::NonNull;
 
 #[derive(Eq)]
@@ -17,23 +20,29 @@ impl TPtrCtr for TInst {
         self.ptr.as_ptr().cast()
     }
 }
+
+impl TPtrFrom for TInst {
+    fn from_ptr(ptr: TPtr) -> Result<Self, Error> {
+        match NonNull::new(ptr) {
+            // todo;; check the meos error
+            None => Err(MeosError(-999)),
+            Some(p) => {
+                let t = Self { ptr: p.cast() };
+                if unsafe { (*t.ptr.as_ptr()).subtype } == ffi::tempSubtype_TINSTANT as u8 {
+                    Ok(t)
+                } else {
+                    Err(WrongTemporalType)
+                }
+            }
+        }
+    }
+}
+
 impl Temporal for TInst {
     fn from_wkt(wkt: &str) -> Result<Self, Error> {
         unsafe {
             let cstr = to_c_str(wkt)?;
-            let ptr = ffi::tgeompoint_in(cstr.as_ptr());
-            if ptr.is_null() {
-                // todo;; check the meos error
-                return Err(MeosError(-999));
-            }
-            let t = Self {
-                ptr: NonNull::new(ptr).unwrap().cast(),
-            };
-            if (*t.ptr.as_ptr()).subtype == ffi::tempSubtype_TINSTANT as u8 {
-                Ok(t)
-            } else {
-                return Err(WrongTemporalType);
-            }
+            Self::from_ptr(ffi::tgeompoint_in(cstr.as_ptr()))
         }
     }
 
@@ -42,6 +51,16 @@ impl Temporal for TInst {
     }
 }
 
+impl Display for TInst {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .as_ewkt(DEFAULT_PRECISION)
+                .map_err(|_| std::fmt::Error)?,
+        )
+    }
+}
+
 impl Drop for TInst {
     fn drop(&mut self) {
         unsafe {
@@ -67,4 +86,40 @@ impl Ord for TInst {
     }
 }
 
-impl TInst {}
+impl TInst {
+    /// a tgeompoint instant at `(x, y)` in `srid`
+    pub fn from_point(x: f64, y: f64, srid: i32, t: impl Into<Timestamp>) -> Result<Self, Error> {
+        let gs = geom_from_wkt(&format!("SRID={srid};POINT({x} {y})"))?;
+        unsafe {
+            let ptr = ffi::tpointinst_make(gs.as_ptr(), t.into().micros());
+            free(gs.as_ptr().cast());
+            Self::from_ptr(ptr.cast())
+        }
+    }
+
+    pub fn from_float(v: f64, t: impl Into<Timestamp>) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::tfloatinst_make(v, t.into().micros()).cast()) }
+    }
+
+    pub fn from_int(v: i32, t: impl Into<Timestamp>) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::tintinst_make(v, t.into().micros()).cast()) }
+    }
+
+    pub fn from_bool(v: bool, t: impl Into<Timestamp>) -> Result<Self, Error> {
+        unsafe { Self::from_ptr(ffi::tboolinst_make(v, t.into().micros()).cast()) }
+    }
+
+    /// the coordinates of a point instant, `z` is `None` for 2D points
+    pub fn coords(&self) -> Option<(f64, f64, Option<f64>)> {
+        if !self.temporal_type().is_ok_and(|t| t.is_point()) {
+            return None;
+        }
+        unsafe {
+            let gs = NonNull::new(ffi::tpoint_start_value(self.ptr()))?;
+            let b = STBox::from_ptr(ffi::geo_to_stbox(gs.as_ptr()));
+            free(gs.as_ptr().cast());
+            let b = b.ok()?;
+            Some((b.xmin()?, b.ymin()?, b.zmin()))
+        }
+    }
+}
@@ -0,0 +1,658 @@
+use std::ptr::{null_mut, NonNull};
+use std::time::Duration;
+
+use libc::free;
+use meos_sys as ffi;
+
+use crate::error::Error;
+use crate::stbox::{space_origin, stboxes_from, STBox};
+use crate::temp::{temp_from, Temporal};
+use crate::tset::TSet;
+use crate::tz::{Interval, Timestamp, ToInterval};
+use crate::{to_c_str, TPtrCtr, TPtrFrom};
+
+/// A stop detected on a temporal point
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stop {
+    /// time weighted centroid of the stop
+    pub centroid: (f64, f64),
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub dwell: Duration,
+}
+
+/// Operations specific to temporal points
+///
+/// These are only meaningful for temporals of a point type, they are an
+/// [Error::WrongTemporalType] on other temporal types.
+pub trait TPoint: Temporal {
+    /// segments where the point stayed within `max_distance` for at least `min_duration`
+    ///
+    /// `None` when there is no such segment.
+    fn stops<D: ToInterval>(
+        &self,
+        max_distance: f64,
+        min_duration: D,
+    ) -> Result<Option<TSet>, Error> {
+        ensure_point(self)?;
+        let min_duration = min_duration.to_interval()?;
+        unsafe {
+            let ptr = ffi::temporal_stops(self.ptr(), max_distance, &min_duration);
+            if ptr.is_null() {
+                return Ok(None);
+            }
+            TSet::from_ptr(ptr.cast()).map(Some)
+        }
+    }
+
+    /// summary of each stop segment, see [TPoint::stops]
+    fn stop_summaries<D: ToInterval>(
+        &self,
+        max_distance: f64,
+        min_duration: D,
+    ) -> Result<Vec<Stop>, Error> {
+        let Some(stops) = self.stops(max_distance, min_duration)? else {
+            return Ok(vec![]);
+        };
+        Ok(stops
+            .sequences()
+            .iter()
+            .filter_map(|seq| {
+                let centroid = centroid(seq)?;
+                let start = seq.start_timestamp();
+                let end = seq.end_timestamp();
+                let dwell = Duration::from_micros((end.micros() - start.micros()).max(0) as u64);
+                Some(Stop {
+                    centroid,
+                    start,
+                    end,
+                    dwell,
+                })
+            })
+            .collect())
+    }
+
+    /// the spatial reference identifier of the point
+    fn srid(&self) -> Result<i32, Error> {
+        ensure_point(self)?;
+        Ok(unsafe { ffi::tpoint_srid(self.ptr()) })
+    }
+
+    /// the point with its SRID replaced, coordinates are unchanged
+    fn set_srid(&self, srid: i32) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        ensure_point(self)?;
+        unsafe { Self::from_ptr(ffi::tpoint_set_srid(self.ptr(), srid)) }
+    }
+
+    /// the point with its coordinates transformed to `srid`
+    fn transform(&self, srid: i32) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        ensure_point(self)?;
+        unsafe { Self::from_ptr(ffi::tpoint_transform(self.ptr(), srid)) }
+    }
+
+    /// the point with its coordinates transformed by a PROJ pipeline
+    ///
+    /// The result is tagged with `srid`, the pipeline is run in reverse when
+    /// `forward` is false.
+    fn transform_pipeline(&self, pipeline: &str, srid: i32, forward: bool) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        ensure_point(self)?;
+        let cstr = to_c_str(pipeline)?;
+        unsafe {
+            Self::from_ptr(ffi::tpoint_transform_pipeline(
+                self.ptr(),
+                cstr.as_ptr(),
+                srid,
+                forward,
+            ))
+        }
+    }
+
+    /// the length travelled, in meters for geographies
+    fn length(&self) -> Result<f64, Error> {
+        ensure_point(self)?;
+        Ok(unsafe { ffi::tpoint_length(self.ptr()) })
+    }
+
+    /// the speed as a tfloat, in meters per second for geographies
+    fn speed(&self) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        ensure_point(self)?;
+        unsafe { Self::from_ptr(ffi::tpoint_speed(self.ptr())) }
+    }
+
+    /// the distance to another point at each instant as a tfloat, in meters for geographies
+    ///
+    /// `None` when the points do not share any instant.
+    fn distance(&self, other: &dyn Temporal) -> Result<Option<Box<dyn Temporal>>, Error> {
+        ensure_point(self)?;
+        ensure_point(other)?;
+        Ok(unsafe { temp_from(ffi::distance_tpoint_tpoint(self.ptr(), other.ptr())) })
+    }
+
+    /// the smallest distance ever between the two points, in meters for geographies
+    fn nearest_approach_distance(&self, other: &dyn Temporal) -> Result<f64, Error> {
+        ensure_point(self)?;
+        ensure_point(other)?;
+        Ok(unsafe { ffi::nad_tpoint_tpoint(self.ptr(), other.ptr()) })
+    }
+
+    /// the point restricted to a box, borders included, `None` when they do not intersect
+    fn at_stbox(&self, b: &STBox) -> Result<Option<Box<dyn Temporal>>, Error> {
+        ensure_point(self)?;
+        Ok(unsafe { temp_from(ffi::tpoint_at_stbox(self.ptr(), b.ptr(), true)) })
+    }
+
+    /// a tbool, true when the two points are within `dist` of each other
+    ///
+    /// `None` when the points do not share any instant.
+    fn tdwithin(
+        &self,
+        other: &dyn Temporal,
+        dist: f64,
+    ) -> Result<Option<Box<dyn Temporal>>, Error> {
+        ensure_point(self)?;
+        ensure_point(other)?;
+        Ok(unsafe {
+            temp_from(ffi::tdwithin_tpoint_tpoint(
+                self.ptr(),
+                other.ptr(),
+                dist,
+                false,
+                false,
+            ))
+        })
+    }
+
+    /// a KML `gx:Track` element with a `when` and a `gx:coord` per instant
+    ///
+    /// The enclosing document has to declare the `gx` namespace,
+    /// `http://www.google.com/kml/ext/2.2`.
+    fn to_kml_track(&self) -> Result<String, Error> {
+        // the n-th when pairs with the n-th coord, so both come from the same
+        // instants
+        let points = positions(self)?
+            .into_iter()
+            .map(|(t, coords)| Ok((t.to_rfc3339().ok_or(Error::TimestampOutOfRange)?, coords)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut kml = String::from("<gx:Track>");
+        for (when, _) in &points {
+            kml.push_str(&format!("<when>{when}</when>"));
+        }
+        for (_, (x, y, z)) in &points {
+            let z = z.map(|z| format!(" {z}")).unwrap_or_default();
+            kml.push_str(&format!("<gx:coord>{x} {y}{z}</gx:coord>"));
+        }
+        kml.push_str("</gx:Track>");
+        Ok(kml)
+    }
+
+    /// a GeoJSON LineString feature with the unix seconds of each vertex in a
+    /// `timestamps` property, as read by the kepler.gl trip layer
+    ///
+    /// A single instant is a Point feature, a LineString needs two positions.
+    fn to_geojson_trip(&self) -> Result<String, Error> {
+        let (mut coords, mut times) = (vec![], vec![]);
+        for (t, (x, y, z)) in positions(self)? {
+            let us = t.unix_micros().ok_or(Error::TimestampOutOfRange)?;
+            coords.push(match z {
+                Some(z) => format!("[{x},{y},{z}]"),
+                None => format!("[{x},{y}]"),
+            });
+            times.push((us as f64 / 1e6).to_string());
+        }
+        let geometry = match coords.as_slice() {
+            [point] => format!(r#"{{"type":"Point","coordinates":{point}}}"#),
+            _ => format!(
+                r#"{{"type":"LineString","coordinates":[{}]}}"#,
+                coords.join(",")
+            ),
+        };
+        Ok(format!(
+            r#"{{"type":"Feature","geometry":{geometry},"properties":{{"timestamps":[{}]}}}}"#,
+            times.join(",")
+        ))
+    }
+
+    /// converts a tgeompoint into a tgeogpoint
+    fn to_geography(&self) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        ensure_point(self)?;
+        unsafe { Self::from_ptr(ffi::tgeompoint_to_tgeogpoint(self.ptr())) }
+    }
+
+    /// converts a tgeogpoint into a tgeompoint
+    fn to_geometry(&self) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        ensure_point(self)?;
+        unsafe { Self::from_ptr(ffi::tgeogpoint_to_tgeompoint(self.ptr())) }
+    }
+
+    /// one box per segment of the temporal
+    fn stboxes(&self) -> Result<Vec<STBox>, Error> {
+        ensure_point(self)?;
+        let mut count = 0;
+        Ok(unsafe { stboxes_from(ffi::tpoint_stboxes(self.ptr(), &mut count), count) })
+    }
+
+    /// at most `n` boxes, each covering consecutive segments of the temporal
+    fn split_n_stboxes(&self, n: i32) -> Result<Vec<STBox>, Error> {
+        ensure_point(self)?;
+        let mut count = 0;
+        Ok(unsafe {
+            stboxes_from(
+                ffi::tpoint_split_n_stboxes(self.ptr(), n, &mut count),
+                count,
+            )
+        })
+    }
+
+    /// fragments of the temporal within each cell of a spatiotemporal grid
+    ///
+    /// The grid is aligned on the spatial origin `sorigin` in the SRID of the
+    /// temporal and on the time `torigin`, see [STBox::tiles].
+    fn split_by_space_time<D: ToInterval>(
+        &self,
+        xsize: f64,
+        ysize: f64,
+        zsize: f64,
+        duration: D,
+        sorigin: (f64, f64, f64),
+        torigin: impl Into<Timestamp>,
+    ) -> Result<Vec<(STBox, Box<dyn Temporal>)>, Error> {
+        ensure_point(self)?;
+        let mut duration = duration.to_interval()?;
+        // the cells are closed-open periods of a fixed width
+        let width = Interval::from(&duration).fixed_micros()?;
+        let bbox = unsafe { STBox::from_ptr(ffi::tpoint_to_stbox(self.ptr()))? };
+        let srid = bbox.srid();
+        let hasz = bbox.has_z();
+        let geodetic = bbox.is_geodetic();
+        let sorigin = space_origin(srid, hasz, sorigin)?;
+
+        let mut space_buckets = null_mut();
+        let mut time_buckets = null_mut();
+        let mut count = 0;
+        unsafe {
+            let frags = ffi::tpoint_space_time_split(
+                self.ptr(),
+                xsize,
+                ysize,
+                zsize,
+                &mut duration,
+                sorigin.as_ptr(),
+                torigin.into().micros(),
+                false,
+                &mut space_buckets,
+                &mut time_buckets,
+                &mut count,
+            );
+            free(sorigin.as_ptr().cast());
+            if frags.is_null() {
+                // todo;; check the meos error
+                return Err(Error::MeosError(-999));
+            }
+
+            // take ownership of every bucket before reporting an error
+            let mut res = Vec::with_capacity(count as usize);
+            for i in 0..count as usize {
+                let gs = *space_buckets.add(i);
+                let t = *time_buckets.add(i);
+                let cell = cell_box(gs, hasz, geodetic, srid, (xsize, ysize, zsize), t, width);
+                free(gs.cast());
+                // todo;; check the meos error
+                let frag = temp_from(*frags.add(i)).ok_or(Error::MeosError(-999));
+                res.push(cell.and_then(|cell| Ok((cell, frag?))));
+            }
+            free(frags.cast());
+            free(space_buckets.cast());
+            free(time_buckets.cast());
+            res.into_iter().collect()
+        }
+    }
+}
+
+impl<T: Temporal> TPoint for T {}
+
+/// the base type of a temporal can only be checked at run time
+pub(crate) fn ensure_point<T: Temporal + ?Sized>(t: &T) -> Result<(), Error> {
+    if t.temporal_type()?.is_point() {
+        Ok(())
+    } else {
+        Err(Error::WrongTemporalType)
+    }
+}
+
+/// the timestamp and coordinates of each instant of a temporal point
+fn positions<T: Temporal + ?Sized>(
+    t: &T,
+) -> Result<Vec<(Timestamp, (f64, f64, Option<f64>))>, Error> {
+    ensure_point(t)?;
+    t.instants()
+        .iter()
+        // todo;; check the meos error
+        .map(|i| {
+            Ok((
+                i.start_timestamp(),
+                i.coords().ok_or(Error::MeosError(-999))?,
+            ))
+        })
+        .collect()
+}
+
+/// the grid cell starting at a space and time bucket
+unsafe fn cell_box(
+    gs: *mut ffi::GSERIALIZED,
+    hasz: bool,
+    geodetic: bool,
+    srid: i32,
+    (xsize, ysize, zsize): (f64, f64, f64),
+    t: i64,
+    width: i64,
+) -> Result<STBox, Error> {
+    let end = t.checked_add(width).ok_or(Error::IntervalOverflow)?;
+    let origin = STBox::from_ptr(ffi::geo_to_stbox(gs))?;
+    let (x, y, z) = {
+        let b = origin.ptr();
+        ((*b).xmin, (*b).ymin, (*b).zmin)
+    };
+    let period = ffi::period_make(t, end, true, false);
+    let b = ffi::stbox_make(
+        true,
+        hasz,
+        geodetic,
+        srid,
+        x,
+        x + xsize,
+        y,
+        y + ysize,
+        z,
+        z + zsize,
+        period,
+    );
+    free(period.cast());
+    STBox::from_ptr(b)
+}
+
+fn centroid<T: Temporal>(t: &T) -> Option<(f64, f64)> {
+    unsafe {
+        let gs = NonNull::new(ffi::tpoint_twcentroid(t.ptr()))?;
+        let b = STBox::from_ptr(ffi::geo_to_stbox(gs.as_ptr()));
+        free(gs.as_ptr().cast());
+        let b = b.ok()?;
+        Some(((*b.ptr()).xmin, (*b.ptr()).ymin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::prelude::*;
+    use crate::tz::{Interval, Timestamp};
+    use crate::{finalize, init};
+
+    #[cfg(test)]
+    #[ctor::ctor]
+    fn meos_init() {
+        init();
+    }
+
+    #[cfg(test)]
+    #[ctor::dtor]
+    fn meos_finalize() {
+        finalize();
+    }
+
+    const WKT: &str =
+        "SRID=4326;[POINT(0 0)@2000-01-01 00:00:00+00, POINT(0 1)@2000-01-02 00:00:00+00]";
+
+    #[test]
+    fn test_geog_length() {
+        let geom = TSeq::from_wkt(WKT).unwrap();
+        assert_eq!(1.0, geom.length().unwrap());
+
+        let geog = TSeq::from_wkt_as(WKT, TemporalType::TGeogPoint).unwrap();
+        assert_eq!(TemporalType::TGeogPoint, geog.temporal_type().unwrap());
+        let len = geog.length().unwrap();
+        assert!((110_000.0..111_000.0).contains(&len), "{len}");
+    }
+
+    #[test]
+    fn test_geog_conversion() {
+        let geom = TSeq::from_wkt(WKT).unwrap();
+        let geog = geom.to_geography().unwrap();
+        assert_eq!(TemporalType::TGeogPoint, geog.temporal_type().unwrap());
+        assert_eq!(4326, geog.srid().unwrap());
+
+        let back = geog.to_geometry().unwrap();
+        assert_eq!(TemporalType::TGeomPoint, back.temporal_type().unwrap());
+        assert!(geom == back);
+    }
+
+    const STOP: &str = "[POINT(0 0)@2000-01-01 00:00:00+00, POINT(0.5 0)@2000-01-01 00:05:00+00, \
+                        POINT(0 0)@2000-01-01 00:10:00+00, POINT(10 10)@2000-01-01 00:20:00+00]";
+
+    #[test]
+    fn test_srid() {
+        let trip = TSeq::from_wkt(WKT).unwrap();
+        assert_eq!(4326, trip.srid().unwrap());
+
+        let tagged = trip.set_srid(3857).unwrap();
+        assert_eq!(3857, tagged.srid().unwrap());
+        assert_eq!(trip.instants()[1].coords(), tagged.instants()[1].coords());
+
+        let merc = trip.transform(3857).unwrap();
+        assert_eq!(3857, merc.srid().unwrap());
+        let (x, y, _) = merc.instants()[1].coords().unwrap();
+        assert!(x.abs() < 1e-6, "{x}");
+        assert!((y - 111_325.14).abs() < 0.01, "{y}");
+    }
+
+    #[test]
+    fn test_transform_pipeline() {
+        let trip = TSeq::from_wkt(WKT).unwrap();
+        let swap = "+proj=pipeline +step +proj=axisswap +order=2,1";
+        let swapped = trip.transform_pipeline(swap, 4326, true).unwrap();
+        assert_eq!(4326, swapped.srid().unwrap());
+        assert_eq!(Some((1.0, 0.0, None)), swapped.instants()[1].coords());
+
+        assert!(matches!(
+            trip.transform_pipeline("+proj=axisswap\0+order=2,1", 4326, true),
+            Err(Error::FfiStringError(_))
+        ));
+    }
+
+    #[test]
+    fn test_distance() {
+        let trip = TSeq::from_wkt(STOP).unwrap();
+        let origin = TSeq::from_wkt(
+            "[POINT(0 0)@2000-01-01 00:00:00+00, POINT(0 0)@2000-01-01 00:20:00+00]",
+        )
+        .unwrap();
+        let d = trip.distance(&origin).unwrap().unwrap();
+        assert_eq!(TemporalType::TFloat, d.temporal_type().unwrap());
+        assert_eq!(Some(0.5), d.value_at(Timestamp::from_micros(300_000_000)));
+
+        let later = TSeq::from_wkt(
+            "[POINT(0 0)@2000-01-02 00:00:00+00, POINT(0 0)@2000-01-02 00:20:00+00]",
+        )
+        .unwrap();
+        assert!(trip.distance(&later).unwrap().is_none());
+        assert!(trip.tdwithin(&later, 1.0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stops() {
+        let trip = TSeq::from_wkt(STOP).unwrap();
+        let five_minutes = Duration::from_secs(300);
+        let stops = trip.stops(1.0, five_minutes).unwrap().unwrap();
+        let seqs = stops.sequences();
+        assert_eq!(1, seqs.len());
+        assert_eq!(3, seqs[0].num_instants());
+
+        let summaries = trip.stop_summaries(1.0, five_minutes).unwrap();
+        assert_eq!(1, summaries.len());
+        let stop = &summaries[0];
+        assert_eq!(Timestamp::from_micros(0), stop.start);
+        assert_eq!(Timestamp::from_micros(600_000_000), stop.end);
+        assert_eq!(Duration::from_secs(600), stop.dwell);
+        assert!((stop.centroid.0 - 0.25).abs() < 1e-9, "{:?}", stop.centroid);
+        assert!(stop.centroid.1.abs() < 1e-9, "{:?}", stop.centroid);
+
+        assert!(trip.stops(0.1, five_minutes).unwrap().is_none());
+        assert!(trip.stop_summaries(0.1, five_minutes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sequences() {
+        let set = TSet::from_wkt(
+            "{[POINT(0 0)@2000-01-01 00:00:00+00, POINT(1 1)@2000-01-01 00:01:00+00], \
+             [POINT(2 2)@2000-01-01 00:05:00+00]}",
+        )
+        .unwrap();
+        let seqs = set.sequences();
+        assert_eq!(2, seqs.len());
+        assert_eq!(2, seqs[0].num_instants());
+        assert_eq!(
+            Timestamp::from_micros(300_000_000),
+            seqs[1].start_timestamp()
+        );
+    }
+
+    #[test]
+    fn test_not_a_point() {
+        let speed = TSeq::from_wkt_as(
+            "[1@2000-01-01 00:00:00+00, 2@2000-01-02 00:00:00+00]",
+            TemporalType::TFloat,
+        )
+        .unwrap();
+        assert!(matches!(
+            speed.stops(1.0, Duration::from_secs(60)),
+            Err(Error::WrongTemporalType)
+        ));
+        assert!(matches!(speed.srid(), Err(Error::WrongTemporalType)));
+        assert!(matches!(speed.length(), Err(Error::WrongTemporalType)));
+        assert!(matches!(
+            speed.distance(&speed),
+            Err(Error::WrongTemporalType)
+        ));
+        assert!(speed.set_srid(4326).is_err());
+    }
+
+    #[test]
+    fn test_stboxes() {
+        let trip = TSeq::from_wkt(
+            "[POINT(0 0)@2000-01-01 00:00:00+00, POINT(1 1)@2000-01-01 00:01:00+00, \
+             POINT(3 1)@2000-01-01 00:02:00+00]",
+        )
+        .unwrap();
+        let boxes = trip.stboxes().unwrap();
+        assert_eq!(2, boxes.len());
+        assert_eq!(Some(1.0), boxes[0].xmax());
+        assert_eq!(Some(1.0), boxes[1].xmin());
+        assert_eq!(Some(3.0), boxes[1].xmax());
+
+        let one = trip.split_n_stboxes(1).unwrap();
+        assert_eq!(1, one.len());
+        assert_eq!(Some(0.0), one[0].xmin());
+        assert_eq!(Some(3.0), one[0].xmax());
+        assert_eq!(2, trip.split_n_stboxes(5).unwrap().len());
+    }
+
+    #[test]
+    fn test_split_by_space_time() {
+        let trip = TSeq::from_wkt(
+            "[POINT(1 1)@2000-01-01 01:00:00+00, POINT(9 1)@2000-01-01 02:00:00+00]",
+        )
+        .unwrap();
+        let day = Duration::from_secs(86_400);
+        let origin = Timestamp::from_micros(0);
+        let frags = trip
+            .split_by_space_time(5.0, 5.0, 0.0, day, (0.0, 0.0, 0.0), origin)
+            .unwrap();
+        assert_eq!(2, frags.len());
+        assert_eq!(Some(0.0), frags[0].0.xmin());
+        assert_eq!(Some(5.0), frags[1].0.xmin());
+        assert_eq!(Some(origin), frags[0].0.tmin());
+
+        let shifted = trip
+            .split_by_space_time(5.0, 5.0, 0.0, day, (2.5, 0.0, 0.0), origin)
+            .unwrap();
+        assert_eq!(3, shifted.len());
+
+        // a day is a fixed width, a month is not
+        let days = Interval {
+            days: 1,
+            ..Default::default()
+        };
+        let by_days = trip
+            .split_by_space_time(5.0, 5.0, 0.0, days, (0.0, 0.0, 0.0), origin)
+            .unwrap();
+        assert_eq!(
+            Some(Timestamp::from_micros(86_400_000_000)),
+            by_days[0].0.tmax()
+        );
+        let month = Interval {
+            months: 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            trip.split_by_space_time(5.0, 5.0, 0.0, month, (0.0, 0.0, 0.0), origin),
+            Err(Error::UnsupportedInterval)
+        ));
+    }
+
+    #[test]
+    fn test_kml_track() {
+        let kml = TSeq::from_wkt(WKT).unwrap().to_kml_track().unwrap();
+        assert_eq!(
+            "<gx:Track><when>2000-01-01T00:00:00Z</when><when>2000-01-02T00:00:00Z</when>\
+             <gx:coord>0 0</gx:coord><gx:coord>0 1</gx:coord></gx:Track>",
+            kml
+        );
+    }
+
+    #[test]
+    fn test_geojson_trip() {
+        let json = TSeq::from_wkt(WKT).unwrap().to_geojson_trip().unwrap();
+        assert_eq!(
+            r#"{"type":"Feature","geometry":{"type":"LineString","coordinates":[[0,0],[0,1]]},"properties":{"timestamps":[946684800,946771200]}}"#,
+            json
+        );
+
+        let json = TInst::from_wkt("POINT(1 2)@2000-01-01 00:00:00+00")
+            .unwrap()
+            .to_geojson_trip()
+            .unwrap();
+        assert_eq!(
+            r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[1,2]},"properties":{"timestamps":[946684800]}}"#,
+            json
+        );
+
+        let temps = TSeq::from_wkt_as(
+            "[1@2000-01-01 00:00:00+00, 2@2000-01-02 00:00:00+00]",
+            TemporalType::TFloat,
+        )
+        .unwrap();
+        assert!(matches!(
+            temps.to_geojson_trip(),
+            Err(Error::WrongTemporalType)
+        ));
+        assert!(temps.to_kml_track().is_err());
+    }
+}
@@ -1,13 +1,12 @@
 use crate::error::Error;
 use crate::error::Error::{MeosError, WrongTemporalType};
 use crate::temp::Temporal;
-use crate::{to_c_str, TPtr, TPtrCtr, Type};
+use crate::{to_c_str, TPtr, TPtrCtr, TPtrFrom, Type, DEFAULT_PRECISION};
 use libc::free;
 use meos_sys as ffi;
 use std::cmp::Ordering;
-use std::ffi::CString;
+use std::fmt::{Display, Formatter};
 use std::ptr::NonNull;
-use std::str::Utf8Error;
 
 #[derive(Eq)]
 pub struct TSeq {
@@ -18,6 +17,24 @@ impl TPtrCtr for TSeq {
         self.ptr.as_ptr().cast()
     }
 }
+
+impl TPtrFrom for TSeq {
+    fn from_ptr(ptr: TPtr) -> Result<Self, Error> {
+        match NonNull::new(ptr) {
+            // todo;; check the meos error
+            None => Err(MeosError(-999)),
+            Some(p) => {
+                let t = Self { ptr: p.cast() };
+                if unsafe { (*t.ptr.as_ptr()).subtype } == ffi::tempSubtype_TSEQUENCE as u8 {
+                    Ok(t)
+                } else {
+                    Err(WrongTemporalType)
+                }
+            }
+        }
+    }
+}
+
 impl Temporal for TSeq {
     fn from_wkt(wkt: &str) -> Result<Self, Error>
     where
@@ -25,19 +42,7 @@ impl Temporal for TSeq {
     {
         unsafe {
             let cstr = to_c_str(wkt)?;
-            let ptr = ffi::tgeompoint_in(cstr.as_ptr());
-            if ptr.is_null() {
-                // todo;; check the meos error
-                return Err(MeosError(-999));
-            }
-            let t = Self {
-                ptr: NonNull::new(ptr).unwrap().cast(),
-            };
-            if (*t.ptr.as_ptr()).subtype == ffi::tempSubtype_TSEQUENCE as u8 {
-                Ok(t)
-            } else {
-                return Err(WrongTemporalType);
-            }
+            Self::from_ptr(ffi::tgeompoint_in(cstr.as_ptr()))
         }
     }
 
@@ -62,13 +67,15 @@ impl TSeq {
         };
         NonNull::new(p).map(|p| TSeq { ptr: p })
     }
+}
 
-    fn out(&self) -> Result<String, Utf8Error> {
-        unsafe {
-            let temp_out = ffi::tsequence_out(self.ptr().cast(), 15);
-            let x = CString::from_raw(temp_out);
-            x.to_str().map(|x| x.to_owned())
-        }
+impl Display for TSeq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .as_ewkt(DEFAULT_PRECISION)
+                .map_err(|_| std::fmt::Error)?,
+        )
     }
 }
 
@@ -1,10 +1,12 @@
 use crate::error::Error;
-use crate::error::Error::{MeosError, WrongTemporalType};
+use crate::error::Error::{InvalidInput, MeosError, WrongTemporalType};
 use crate::temp::Temporal;
-use crate::{to_c_str, TPtrCtr, Type};
+use crate::tseq::TSeq;
+use crate::{to_c_str, TPtr, TPtrCtr, TPtrFrom, Type, DEFAULT_PRECISION};
 use libc::free;
 use meos_sys as ffi;
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use std::ptr::NonNull;
 
 #[derive(Eq)]
@@ -18,6 +20,23 @@ impl TPtrCtr for TSet {
     }
 }
 
+impl TPtrFrom for TSet {
+    fn from_ptr(ptr: TPtr) -> Result<Self, Error> {
+        match NonNull::new(ptr) {
+            // todo;; check the meos error
+            None => Err(MeosError(-999)),
+            Some(p) => {
+                let t = Self { ptr: p.cast() };
+                if unsafe { (*t.ptr.as_ptr()).subtype } == ffi::tempSubtype_TSEQUENCESET as u8 {
+                    Ok(t)
+                } else {
+                    Err(WrongTemporalType)
+                }
+            }
+        }
+    }
+}
+
 impl Temporal for TSet {
     fn from_wkt(wkt: &str) -> Result<Self, Error>
     where
@@ -25,19 +44,7 @@ impl Temporal for TSet {
     {
         unsafe {
             let cstr = to_c_str(wkt)?;
-            let ptr = ffi::tgeompoint_in(cstr.as_ptr());
-            if ptr.is_null() {
-                // todo;; check the meos error
-                return Err(MeosError(-999));
-            }
-            let t = Self {
-                ptr: NonNull::new(ptr).unwrap().cast(),
-            };
-            if (*t.ptr.as_ptr()).subtype == ffi::tempSubtype_TSEQUENCESET as u8 {
-                Ok(t)
-            } else {
-                return Err(WrongTemporalType);
-            }
+            Self::from_ptr(ffi::tgeompoint_in(cstr.as_ptr()))
         }
     }
 
@@ -46,7 +53,52 @@ impl Temporal for TSet {
     }
 }
 
-impl TSet {}
+impl TSet {
+    /// a sequence set of the sequences, which are normalized
+    ///
+    /// An error for no sequences, which meos can't make a set of.
+    pub fn make(seqs: &[TSeq]) -> Result<Self, Error> {
+        if seqs.is_empty() {
+            return Err(InvalidInput("a sequence set needs sequences".into()));
+        }
+        let count = i32::try_from(seqs.len())
+            .map_err(|_| InvalidInput("too many sequences for a sequence set".into()))?;
+        let v: Vec<TPtr> = seqs.iter().map(|s| s.ptr()).collect();
+        unsafe {
+            Self::from_ptr(
+                ffi::tsequenceset_make(v.as_ptr() as *mut *const ffi::TSequence, count, true)
+                    .cast(),
+            )
+        }
+    }
+
+    /// the sequences composing this set
+    pub fn sequences(&self) -> Vec<TSeq> {
+        let mut count = 0;
+        unsafe {
+            let seqs = ffi::temporal_sequences(self.ptr(), &mut count);
+            if seqs.is_null() {
+                return vec![];
+            }
+            let res = std::slice::from_raw_parts(seqs, count as usize)
+                .iter()
+                .filter_map(|&p| TSeq::from_ptr(p.cast()).ok())
+                .collect();
+            free(seqs.cast());
+            res
+        }
+    }
+}
+
+impl Display for TSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .as_ewkt(DEFAULT_PRECISION)
+                .map_err(|_| std::fmt::Error)?,
+        )
+    }
+}
 
 impl Drop for TSet {
     fn drop(&mut self) {
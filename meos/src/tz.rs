@@ -0,0 +1,462 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use meos_sys as ffi;
+
+use crate::error::Error;
+use crate::error::Error::{IntervalOverflow, TimestampOutOfRange, UnsupportedInterval};
+
+/// Microseconds between the unix epoch and the postgres epoch of 2000-01-01
+const POSTGRES_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+const USECS_PER_DAY: i64 = 86_400_000_000;
+
+/// Default origin of meos time bins, Monday 2000-01-03
+pub const DEFAULT_TIME_ORIGIN: Timestamp = Timestamp::from_micros(172_800_000_000);
+
+/// A meos `TimestampTz`, microseconds since 2000-01-01 00:00:00 UTC
+///
+/// Converts to and from [SystemTime], and with the `chrono` and `time` features
+/// to and from their date time types.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// the meos `-infinity` timestamp
+    pub const NEG_INFINITY: Timestamp = Timestamp(i64::MIN);
+
+    /// the meos `infinity` timestamp
+    pub const INFINITY: Timestamp = Timestamp(i64::MAX);
+
+    pub const fn from_micros(us: i64) -> Self {
+        Self(us)
+    }
+
+    /// microseconds since 2000-01-01 00:00:00 UTC
+    pub const fn micros(&self) -> i64 {
+        self.0
+    }
+
+    /// `None` when the time is before the range of meos timestamps
+    pub const fn from_unix_micros(us: i64) -> Option<Self> {
+        match us.checked_sub(POSTGRES_EPOCH_MICROS) {
+            Some(us) => Some(Self(us)),
+            None => None,
+        }
+    }
+
+    /// microseconds since 1970-01-01 00:00:00 UTC, `None` when they overflow
+    /// as for [Timestamp::INFINITY]
+    pub const fn unix_micros(&self) -> Option<i64> {
+        self.0.checked_add(POSTGRES_EPOCH_MICROS)
+    }
+
+    /// the UTC RFC 3339 form, with a fraction only when there are sub seconds
+    ///
+    /// `None` when the timestamp has no unix time, as for [Timestamp::INFINITY].
+    pub fn to_rfc3339(&self) -> Option<String> {
+        let us = self.unix_micros()?;
+        let (days, us) = (us.div_euclid(USECS_PER_DAY), us.rem_euclid(USECS_PER_DAY));
+        let (y, m, d) = civil_from_days(days);
+        let secs = us / 1_000_000;
+        let frac = match us % 1_000_000 {
+            0 => String::new(),
+            f => format!(".{f:06}"),
+        };
+        Some(format!(
+            "{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}{frac}Z",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        ))
+    }
+}
+
+/// the proleptic gregorian date of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+impl From<i64> for Timestamp {
+    fn from(us: i64) -> Self {
+        Self::from_micros(us)
+    }
+}
+
+impl From<Timestamp> for i64 {
+    fn from(t: Timestamp) -> Self {
+        t.micros()
+    }
+}
+
+impl TryFrom<SystemTime> for Timestamp {
+    type Error = Error;
+
+    fn try_from(t: SystemTime) -> Result<Self, Self::Error> {
+        let us = match t.duration_since(UNIX_EPOCH) {
+            Ok(d) => i64::try_from(d.as_micros()),
+            Err(e) => i64::try_from(e.duration().as_micros()).map(|us| -us),
+        };
+        us.ok()
+            .and_then(Self::from_unix_micros)
+            .ok_or(TimestampOutOfRange)
+    }
+}
+
+impl TryFrom<Timestamp> for SystemTime {
+    type Error = Error;
+
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        let us = t.unix_micros().ok_or(TimestampOutOfRange)?;
+        let d = Duration::from_micros(us.unsigned_abs());
+        let t = if us >= 0 {
+            UNIX_EPOCH.checked_add(d)
+        } else {
+            UNIX_EPOCH.checked_sub(d)
+        };
+        t.ok_or(TimestampOutOfRange)
+    }
+}
+
+/// chrono dates always fit, and the earliest saturate to `-infinity`
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for Timestamp {
+    fn from(t: chrono::DateTime<Tz>) -> Self {
+        Self::from_unix_micros(t.timestamp_micros()).unwrap_or(Self::NEG_INFINITY)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        t.unix_micros()
+            .and_then(chrono::DateTime::from_timestamp_micros)
+            .ok_or(TimestampOutOfRange)
+    }
+}
+
+/// time dates always fit in meos timestamps
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Timestamp {
+    fn from(t: time::OffsetDateTime) -> Self {
+        let us = (t.unix_timestamp_nanos() / 1000) as i64;
+        Self::from_unix_micros(us).unwrap_or(Self::NEG_INFINITY)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Timestamp> for time::OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(t: Timestamp) -> Result<Self, Self::Error> {
+        let us = t.unix_micros().ok_or(TimestampOutOfRange)?;
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(us) * 1000)
+            .map_err(|_| TimestampOutOfRange)
+    }
+}
+
+/// A meos `Interval`, a duration split into months, days and microseconds
+///
+/// Months and days have no fixed length so an interval using them cannot be
+/// converted back to a fixed duration.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+
+impl Interval {
+    pub const fn from_micros(us: i64) -> Self {
+        Self {
+            months: 0,
+            days: 0,
+            micros: us,
+        }
+    }
+
+    /// the fixed length of the interval, `None` when it has a month component
+    /// or does not fit in `i64` microseconds
+    pub const fn total_micros(&self) -> Option<i64> {
+        if self.months != 0 {
+            return None;
+        }
+        match (self.days as i64).checked_mul(USECS_PER_DAY) {
+            Some(days) => days.checked_add(self.micros),
+            None => None,
+        }
+    }
+
+    /// [Interval::total_micros] telling a month component from an overflow
+    pub(crate) fn fixed_micros(&self) -> Result<i64, Error> {
+        if self.months != 0 {
+            return Err(UnsupportedInterval);
+        }
+        self.total_micros().ok_or(IntervalOverflow)
+    }
+}
+
+impl From<&ffi::Interval> for Interval {
+    fn from(i: &ffi::Interval) -> Self {
+        Self {
+            months: i.month,
+            days: i.day,
+            micros: i.time,
+        }
+    }
+}
+
+impl TryFrom<Duration> for Interval {
+    type Error = Error;
+
+    fn try_from(d: Duration) -> Result<Self, Self::Error> {
+        let us = d.as_micros().try_into().map_err(|_| IntervalOverflow)?;
+        Ok(Self::from_micros(us))
+    }
+}
+
+impl TryFrom<Interval> for Duration {
+    type Error = Error;
+
+    fn try_from(i: Interval) -> Result<Self, Self::Error> {
+        match i.fixed_micros()? {
+            us if us >= 0 => Ok(Duration::from_micros(us as u64)),
+            _ => Err(UnsupportedInterval),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::Duration> for Interval {
+    type Error = Error;
+
+    fn try_from(d: chrono::Duration) -> Result<Self, Self::Error> {
+        let us = d.num_microseconds().ok_or(IntervalOverflow)?;
+        Ok(Self::from_micros(us))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Interval> for chrono::Duration {
+    type Error = Error;
+
+    fn try_from(i: Interval) -> Result<Self, Self::Error> {
+        i.fixed_micros().map(chrono::Duration::microseconds)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::Duration> for Interval {
+    type Error = Error;
+
+    fn try_from(d: time::Duration) -> Result<Self, Self::Error> {
+        let us = d
+            .whole_microseconds()
+            .try_into()
+            .map_err(|_| IntervalOverflow)?;
+        Ok(Self::from_micros(us))
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Interval> for time::Duration {
+    type Error = Error;
+
+    fn try_from(i: Interval) -> Result<Self, Self::Error> {
+        i.fixed_micros().map(time::Duration::microseconds)
+    }
+}
+
+/// Conversion of a duration into a meos `Interval`
+///
+/// Implemented for [Interval], [Duration] and, with the `chrono` and `time`
+/// features, their duration types. Durations beyond `i64` microseconds are an
+/// [Error::IntervalOverflow].
+pub trait ToInterval {
+    fn to_interval(&self) -> Result<ffi::Interval, Error>;
+}
+
+impl ToInterval for Interval {
+    fn to_interval(&self) -> Result<ffi::Interval, Error> {
+        Ok(ffi::Interval {
+            time: self.micros,
+            day: self.days,
+            month: self.months,
+        })
+    }
+}
+
+impl ToInterval for Duration {
+    fn to_interval(&self) -> Result<ffi::Interval, Error> {
+        Interval::try_from(*self)?.to_interval()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToInterval for chrono::Duration {
+    fn to_interval(&self) -> Result<ffi::Interval, Error> {
+        Interval::try_from(*self)?.to_interval()
+    }
+}
+
+#[cfg(feature = "time")]
+impl ToInterval for time::Duration {
+    fn to_interval(&self) -> Result<ffi::Interval, Error> {
+        Interval::try_from(*self)?.to_interval()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use crate::error::Error;
+    use crate::tz::{Interval, Timestamp, ToInterval};
+
+    #[test]
+    fn test_timestamp_epoch() {
+        let t = Timestamp::from_unix_micros(946_684_800_000_000).unwrap();
+        assert_eq!(0, t.micros());
+
+        let st = SystemTime::try_from(t).unwrap();
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(946_684_800), st);
+        assert_eq!(t, Timestamp::try_from(st).unwrap());
+
+        let before = Timestamp::try_from(UNIX_EPOCH).unwrap();
+        assert_eq!(-946_684_800_000_000, before.micros());
+        assert_eq!(UNIX_EPOCH, SystemTime::try_from(before).unwrap());
+    }
+
+    #[test]
+    fn test_timestamp_infinity() {
+        assert_eq!(None, Timestamp::INFINITY.unix_micros());
+        assert_eq!(None, Timestamp::from_unix_micros(i64::MIN));
+        assert!(SystemTime::try_from(Timestamp::INFINITY).is_err());
+        assert_eq!(None, Timestamp::NEG_INFINITY.to_rfc3339());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_timestamp_chrono() {
+        use chrono::{DateTime, Utc};
+
+        let t = Timestamp::from_micros(86_400_000_000);
+        let dt = DateTime::<Utc>::try_from(t).unwrap();
+        assert_eq!("2000-01-02T00:00:00+00:00", dt.to_rfc3339());
+        assert_eq!(t, Timestamp::from(dt));
+        assert!(DateTime::<Utc>::try_from(Timestamp::INFINITY).is_err());
+        assert!(DateTime::<Utc>::try_from(Timestamp::NEG_INFINITY).is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_timestamp_time() {
+        use time::OffsetDateTime;
+
+        let t = Timestamp::from_micros(86_400_000_000);
+        let dt = OffsetDateTime::try_from(t).unwrap();
+        assert_eq!(2000, dt.year());
+        assert_eq!(t, Timestamp::from(dt));
+        assert!(OffsetDateTime::try_from(Timestamp::INFINITY).is_err());
+        // year 10000, beyond the default range of the time crate
+        let far = Timestamp::from_micros(8_000 * 366 * 86_400_000_000);
+        assert!(OffsetDateTime::try_from(far).is_err());
+    }
+
+    #[test]
+    fn test_timestamp_rfc3339() {
+        assert_eq!(
+            Some("2000-01-01T00:00:00Z".to_string()),
+            Timestamp::from_micros(0).to_rfc3339()
+        );
+        assert_eq!(
+            Some("1999-12-31T23:59:59.999999Z".to_string()),
+            Timestamp::from_micros(-1).to_rfc3339()
+        );
+        let leap = Timestamp::from_micros(59 * 86_400_000_000 + 3_600_000_000);
+        assert_eq!(Some("2000-02-29T01:00:00Z".to_string()), leap.to_rfc3339());
+    }
+
+    #[test]
+    fn test_timestamp_rfc3339_dates() {
+        use chrono::{DateTime, SecondsFormat};
+
+        // from 0001-01-01 to 9999-12-31 in steps of about 90 days and a fraction
+        let (first, last) = (-62_135_596_800_000_000_i64, 253_402_300_799_000_000_i64);
+        for us in (first..last).step_by(7_777_777_777_777) {
+            let dt = DateTime::from_timestamp(us.div_euclid(1_000_000), 0)
+                .unwrap()
+                .checked_add_signed(chrono::Duration::microseconds(us.rem_euclid(1_000_000)))
+                .unwrap();
+            let format = match us % 1_000_000 {
+                0 => SecondsFormat::Secs,
+                _ => SecondsFormat::Micros,
+            };
+            assert_eq!(
+                Some(dt.to_rfc3339_opts(format, true)),
+                Timestamp::from_unix_micros(us).unwrap().to_rfc3339()
+            );
+        }
+    }
+
+    #[test]
+    fn test_interval_duration() {
+        let i = Interval::try_from(Duration::from_secs(90)).unwrap();
+        assert_eq!(90_000_000, i.micros);
+        assert_eq!(Duration::from_secs(90), Duration::try_from(i).unwrap());
+
+        let i = Interval {
+            months: 0,
+            days: 1,
+            micros: 0,
+        };
+        assert_eq!(Duration::from_secs(86_400), Duration::try_from(i).unwrap());
+
+        let i = Interval {
+            months: 1,
+            days: 0,
+            micros: 0,
+        };
+        assert!(matches!(
+            Duration::try_from(i),
+            Err(Error::UnsupportedInterval)
+        ));
+    }
+
+    #[test]
+    fn test_interval_overflow() {
+        assert!(Interval::try_from(Duration::MAX).is_err());
+        assert!(Duration::MAX.to_interval().is_err());
+        assert_eq!(
+            90_000_000,
+            Duration::from_secs(90).to_interval().unwrap().time
+        );
+
+        let i = Interval {
+            months: 0,
+            days: i32::MAX,
+            micros: 0,
+        };
+        assert_eq!(None, i.total_micros());
+        assert!(matches!(
+            Duration::try_from(i),
+            Err(Error::IntervalOverflow)
+        ));
+        let i = Interval {
+            months: 0,
+            days: 1,
+            micros: i64::MAX,
+        };
+        assert_eq!(None, i.total_micros());
+    }
+}